@@ -47,9 +47,20 @@ distr_range_int!(distr_range_i8, i8, 20i8, 100);
 distr_range_int!(distr_range_i16, i16, -500i16, 2000);
 distr_range_int!(distr_range_i32, i32, -200_000_000i32, 800_000_000);
 distr_range_int!(distr_range_i64, i64, 3i64, 134217671);
+#[cfg(feature = "i128_support")]
+distr_range_int!(distr_range_i128, i128, -12345678901234i128, 12345678901234567890);
+distr_range_int!(distr_range_u8, u8, 20u8, 171);
+distr_range_int!(distr_range_u16, u16, 500u16, 32971);
+distr_range_int!(distr_range_u32, u32, 200_000_000u32, 3_800_000_000);
+distr_range_int!(distr_range_u64, u64, 3u64, 0xc000_0000_0000_0000);
+#[cfg(feature = "i128_support")]
+distr_range_int!(distr_range_u128, u128, 3u128, 0xc000_0000_0000_0000_0000_0000_0000_0000);
 
 macro_rules! distr_float {
     ($fnn:ident, $distr:expr) => {
+        distr_float!($fnn, f64, $distr);
+    };
+    ($fnn:ident, $ty:ty, $distr:expr) => {
         #[bench]
         fn $fnn(b: &mut Bencher) {
             let mut rng = XorShiftRng::new().unwrap();
@@ -57,11 +68,11 @@ macro_rules! distr_float {
 
             b.iter(|| {
                 for _ in 0..::RAND_BENCH_N {
-                    let x: f64 = distr.sample(&mut rng);
+                    let x: $ty = distr.sample(&mut rng);
                     black_box(x);
                 }
             });
-            b.bytes = size_of::<f64>() as u64 * ::RAND_BENCH_N;
+            b.bytes = size_of::<$ty>() as u64 * ::RAND_BENCH_N;
         }
     }
 }
@@ -75,3 +86,8 @@ distr_float!(distr_normal, Normal::new(-2.71828, 3.14159));
 distr_float!(distr_log_normal, LogNormal::new(-2.71828, 3.14159));
 distr_float!(distr_gamma_large_shape, Gamma::new(10., 1.0));
 distr_float!(distr_gamma_small_shape, Gamma::new(0.1, 1.0));
+distr_float!(distr_exp_f32, f32, Exp::new(2.71828f32 * 3.14159));
+distr_float!(distr_normal_f32, f32, Normal::new(-2.71828f32, 3.14159));
+distr_float!(distr_log_normal_f32, f32, LogNormal::new(-2.71828f32, 3.14159));
+distr_float!(distr_gamma_large_shape_f32, f32, Gamma::new(10f32, 1.0));
+distr_float!(distr_gamma_small_shape_f32, f32, Gamma::new(0.1f32, 1.0));
@@ -3,11 +3,47 @@
 extern crate test;
 extern crate rand;
 
+const RAND_BENCH_N: u64 = 1000;
+
 use test::{black_box, Bencher};
-use rand::NewSeeded;
+use rand::{NewSeeded, Sample};
+use rand::distributions::{Distribution, Range};
 use rand::prng::XorShiftRng;
 use rand::sequences::{sample, Shuffle};
 
+macro_rules! misc_gen_range {
+    ($fnn:ident, $fnn_new:ident, $ty:ty, $low:expr, $high:expr) => {
+        // The bounds pass through `black_box`, so that setting up the range
+        // cannot be moved out of the loop.
+        #[bench]
+        fn $fnn(b: &mut Bencher) {
+            let mut rng = XorShiftRng::new().unwrap();
+            b.iter(|| {
+                for _ in 0..::RAND_BENCH_N {
+                    let x: $ty = rng.gen_range(black_box($low)..black_box($high));
+                    black_box(x);
+                }
+            });
+        }
+
+        // the same, constructing a `Range` for every sample
+        #[bench]
+        fn $fnn_new(b: &mut Bencher) {
+            let mut rng = XorShiftRng::new().unwrap();
+            b.iter(|| {
+                for _ in 0..::RAND_BENCH_N {
+                    let x: $ty = Range::new(black_box($low), black_box($high)).sample(&mut rng);
+                    black_box(x);
+                }
+            });
+        }
+    }
+}
+
+misc_gen_range!(misc_gen_range_i8, misc_range_new_sample_i8, i8, -20i8, 100);
+misc_gen_range!(misc_gen_range_u32, misc_range_new_sample_u32, u32, 10u32, 2_000_000);
+misc_gen_range!(misc_gen_range_u64, misc_range_new_sample_u64, u64, 10u64, 1 << 40);
+
 #[bench]
 fn misc_shuffle_100(b: &mut Bencher) {
     let mut rng = XorShiftRng::new().unwrap();
@@ -26,3 +62,79 @@ fn misc_sample_10_of_100(b: &mut Bencher) {
         black_box(sample(&mut rng, x, 10));
     })
 }
+
+#[bench]
+fn misc_choose_1_of_100(b: &mut Bencher) {
+    use rand::sequences::Choose;
+
+    let mut rng = XorShiftRng::new().unwrap();
+    let x : &[usize] = &[1; 100];
+    b.iter(|| {
+        for _ in 0..::RAND_BENCH_N {
+            black_box(x.choose(&mut rng));
+        }
+    })
+}
+
+#[bench]
+fn misc_gen_string_alphanumeric_16(b: &mut Bencher) {
+    use rand::distributions::Alphanumeric;
+
+    let mut rng = XorShiftRng::new().unwrap();
+    b.iter(|| {
+        black_box(rng.gen_string(Alphanumeric, 16));
+    });
+    b.bytes = 16;
+}
+
+#[bench]
+fn misc_collect_ascii_word_char_16(b: &mut Bencher) {
+    use rand::distributions::AsciiWordChar;
+
+    let mut rng = XorShiftRng::new().unwrap();
+    b.iter(|| {
+        let s: String = rng.sample_iter(AsciiWordChar).take(16).collect();
+        black_box(s);
+    });
+    b.bytes = 16;
+}
+
+#[bench]
+fn misc_gen_string_charset_16(b: &mut Bencher) {
+    use rand::distributions::Charset;
+
+    let mut rng = XorShiftRng::new().unwrap();
+    let charset = Charset::new("ABCDEFGHJKLMNPQRSTUVWXYZ23456789");
+    b.iter(|| {
+        black_box(rng.gen_string(&charset, 16));
+    });
+    b.bytes = 16;
+}
+
+#[bench]
+fn misc_weighted_choice_1000(b: &mut Bencher) {
+    use rand::sequences::{Weighted, WeightedChoice};
+
+    let mut rng = XorShiftRng::new().unwrap();
+    let items = (0..1000).map(|i| Weighted { weight: i % 7 + 1, item: i }).collect();
+    let choice = WeightedChoice::new(items);
+    b.iter(|| {
+        for _ in 0..::RAND_BENCH_N {
+            black_box(choice.sample(&mut rng));
+        }
+    })
+}
+
+#[bench]
+fn misc_alias_choice_1000(b: &mut Bencher) {
+    use rand::sequences::AliasChoice;
+
+    let mut rng = XorShiftRng::new().unwrap();
+    let weights: Vec<u32> = (0..1000).map(|i| i % 7 + 1).collect();
+    let choice = AliasChoice::new((0..1000).collect(), &weights);
+    b.iter(|| {
+        for _ in 0..::RAND_BENCH_N {
+            black_box(choice.sample(&mut rng));
+        }
+    })
+}
@@ -0,0 +1,303 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support for `#[derive(Rand)]`.
+//!
+//! Deriving `Rand` on a struct or enum implements
+//! `Distribution<T> for Default`, so that values of the type can be generated
+//! with `rng.gen()`.
+//!
+//! ## Structs
+//!
+//! Each field is sampled in order of declaration, by default with the
+//! `Default` distribution. A field may instead be given:
+//!
+//! *   `#[rand(range = "low..high")]` or `#[rand(range = "low..=high")]` to
+//!     sample from the range with `gen_range`
+//! *   `#[rand(distr = "expr")]` to sample from the distribution `expr`, which
+//!     is evaluated each time a value is generated
+//!
+//! ## Enums
+//!
+//! A variant is chosen uniformly at random, then its fields (if any) are
+//! sampled as for a struct. Variants may be given a relative probability with
+//! `#[rand(weight = N)]`; the default weight is 1, and a weight of 0 means the
+//! variant is never generated.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate rand;
+//! #[macro_use]
+//! extern crate rand_derive;
+//!
+//! use rand::{Sample, thread_rng};
+//! use rand::distributions::Normal;
+//!
+//! #[derive(Rand, Debug)]
+//! struct Point {
+//!     #[rand(range = "0..10")]
+//!     x: i32,
+//!     #[rand(distr = "Normal::new(0.0, 1.0)")]
+//!     y: f64,
+//!     label: Option<char>,
+//! }
+//!
+//! #[derive(Rand, Debug)]
+//! enum Shape {
+//!     Dot(Point),
+//!     #[rand(weight = 3)]
+//!     Line { from: Point, to: Point },
+//!     Empty,
+//! }
+//!
+//! fn main() {
+//!     let mut rng = thread_rng();
+//!     let p: Point = rng.gen();
+//!     assert!(0 <= p.x && p.x < 10);
+//!     let _: Shape = rng.gen();
+//! }
+//! ```
+
+#![recursion_limit = "128"]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Attribute, Data, DeriveInput, Expr, Fields, Lit, Meta, NestedMeta};
+use syn::spanned::Spanned;
+
+#[proc_macro_derive(Rand, attributes(rand))]
+pub fn derive_rand(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// How a single field is sampled.
+enum FieldDistr {
+    Default,
+    Range(Box<Expr>, Box<Expr>, bool),
+    Distr(Box<Expr>),
+}
+
+/// The options given in `#[rand(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    range: Option<(Expr, Expr, bool)>,
+    distr: Option<Expr>,
+    weight: Option<u32>,
+}
+
+fn parse_options(attrs: &[Attribute]) -> syn::Result<Options> {
+    let mut opts = Options::default();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("rand")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new_spanned(meta,
+                "expected `#[rand(...)]`")),
+        };
+        for nested in list.nested.iter() {
+            let nv = match *nested {
+                NestedMeta::Meta(Meta::NameValue(ref nv)) => nv,
+                ref other => return Err(syn::Error::new_spanned(other,
+                    "expected `range = \"...\"`, `distr = \"...\"` or \
+                     `weight = N`")),
+            };
+            if nv.path.is_ident("range") {
+                opts.range = Some(parse_range(&nv.lit)?);
+            } else if nv.path.is_ident("distr") {
+                opts.distr = Some(parse_str_expr(&nv.lit)?);
+            } else if nv.path.is_ident("weight") {
+                opts.weight = Some(match nv.lit {
+                    Lit::Int(ref int) => int.base10_parse()?,
+                    ref lit => return Err(syn::Error::new_spanned(lit,
+                        "expected an integer weight")),
+                });
+            } else {
+                return Err(syn::Error::new_spanned(&nv.path,
+                    "unknown `rand` option"));
+            }
+        }
+    }
+    Ok(opts)
+}
+
+fn parse_str_expr(lit: &Lit) -> syn::Result<Expr> {
+    match *lit {
+        Lit::Str(ref s) => s.parse(),
+        ref lit => Err(syn::Error::new_spanned(lit, "expected a string")),
+    }
+}
+
+fn parse_range(lit: &Lit) -> syn::Result<(Expr, Expr, bool)> {
+    if let Expr::Range(range) = parse_str_expr(lit)? {
+        let inclusive = match range.limits {
+            syn::RangeLimits::HalfOpen(_) => false,
+            syn::RangeLimits::Closed(_) => true,
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            return Ok((*from, *to, inclusive));
+        }
+    }
+    Err(syn::Error::new_spanned(lit,
+        "expected a range `low..high` or `low..=high`"))
+}
+
+fn field_distr(attrs: &[Attribute]) -> syn::Result<FieldDistr> {
+    let opts = parse_options(attrs)?;
+    let span = || attrs.iter().find(|a| a.path.is_ident("rand")).unwrap().span();
+    if opts.weight.is_some() {
+        return Err(syn::Error::new(span(),
+            "`weight` may only be used on enum variants"));
+    }
+    match (opts.range, opts.distr) {
+        (None, None) => Ok(FieldDistr::Default),
+        (Some((low, high, inclusive)), None) =>
+            Ok(FieldDistr::Range(Box::new(low), Box::new(high), inclusive)),
+        (None, Some(distr)) => Ok(FieldDistr::Distr(Box::new(distr))),
+        (Some(_), Some(_)) => Err(syn::Error::new(span(),
+            "`range` and `distr` may not both be given")),
+    }
+}
+
+/// Generate the expression sampling one field, and record the field type if
+/// it uses the `Default` distribution (so that it can be bounded).
+fn sample_field(field: &syn::Field, bounds: &mut Vec<syn::Type>)
+    -> syn::Result<TokenStream2>
+{
+    let ty = &field.ty;
+    Ok(match field_distr(&field.attrs)? {
+        FieldDistr::Default => {
+            bounds.push(field.ty.clone());
+            quote! { ::rand::Sample::gen(__rng) }
+        }
+        FieldDistr::Range(low, high, false) => quote! {
+            ::rand::Sample::gen_range::<#ty, _>(__rng, (#low)..(#high))
+        },
+        FieldDistr::Range(low, high, true) => quote! {
+            ::rand::Sample::gen_range::<#ty, _>(__rng, (#low)..=(#high))
+        },
+        FieldDistr::Distr(distr) => quote! {
+            ::rand::distributions::Distribution::sample(&(#distr), __rng)
+        },
+    })
+}
+
+/// Generate the expression constructing `path` with sampled fields.
+fn construct(path: TokenStream2, fields: &Fields, bounds: &mut Vec<syn::Type>)
+    -> syn::Result<TokenStream2>
+{
+    Ok(match *fields {
+        Fields::Named(ref fields) => {
+            let mut values = Vec::new();
+            for field in fields.named.iter() {
+                let name = &field.ident;
+                let value = sample_field(field, bounds)?;
+                values.push(quote! { #name: #value });
+            }
+            quote! { #path { #(#values,)* } }
+        }
+        Fields::Unnamed(ref fields) => {
+            let mut values = Vec::new();
+            for field in fields.unnamed.iter() {
+                values.push(sample_field(field, bounds)?);
+            }
+            quote! { #path ( #(#values,)* ) }
+        }
+        Fields::Unit => path,
+    })
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut bounds = Vec::new();
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            if let Some(attr) = input.attrs.iter().find(|a| a.path.is_ident("rand")) {
+                return Err(syn::Error::new_spanned(attr,
+                    "`rand` options are not supported on structs"));
+            }
+            construct(quote! { #name }, &data.fields, &mut bounds)?
+        }
+        Data::Enum(ref data) => {
+            let mut arms = Vec::new();
+            let mut total = 0u32;
+            for variant in data.variants.iter() {
+                let weight = parse_options(&variant.attrs).and_then(|opts| {
+                    if opts.range.is_some() || opts.distr.is_some() {
+                        return Err(syn::Error::new_spanned(&variant.ident,
+                            "only `weight` may be used on enum variants"));
+                    }
+                    Ok(opts.weight.unwrap_or(1))
+                })?;
+                if weight == 0 {
+                    continue;
+                }
+                total = total.checked_add(weight).ok_or_else(||
+                    syn::Error::new_spanned(&variant.ident,
+                        "the total weight of all variants overflows a `u32`"))?;
+                let ident = &variant.ident;
+                let value = construct(quote! { #name::#ident }, &variant.fields,
+                                      &mut bounds)?;
+                arms.push(quote! {
+                    if __x < #total {
+                        return #value;
+                    }
+                });
+            }
+            if total == 0 {
+                return Err(syn::Error::new_spanned(name,
+                    "`Rand` cannot be derived for an enum without variants \
+                     of non-zero weight"));
+            }
+            quote! {
+                let __x: u32 = ::rand::Sample::gen_range(__rng, 0..#total);
+                #(#arms)*
+                unreachable!()
+            }
+        }
+        Data::Union(_) => return Err(syn::Error::new_spanned(name,
+            "`Rand` cannot be derived for unions")),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut predicates: Vec<TokenStream2> = match where_clause {
+        Some(clause) => clause.predicates.iter().map(|p| quote! { #p }).collect(),
+        None => Vec::new(),
+    };
+    // only bound generic field types; concrete types are checked directly
+    if !input.generics.params.is_empty() {
+        predicates.extend(bounds.iter().map(|ty| quote! {
+            ::rand::distributions::Default: ::rand::distributions::Distribution<#ty>
+        }));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::rand::distributions::Distribution<#name #ty_generics>
+            for ::rand::distributions::Default
+            where #(#predicates,)*
+        {
+            fn sample<__R: ::rand::Rng + ?Sized>(&self, __rng: &mut __R)
+                -> #name #ty_generics
+            {
+                #body
+            }
+        }
+    })
+}
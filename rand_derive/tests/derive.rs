@@ -0,0 +1,115 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate rand;
+#[macro_use]
+extern crate rand_derive;
+
+use rand::{Sample, thread_rng};
+use rand::distributions::{Exp, Normal};
+use rand::mock::StepRng;
+
+#[derive(Rand, Debug, PartialEq)]
+struct Unit;
+
+#[derive(Rand, Debug, PartialEq)]
+struct Tuple(u64, u64);
+
+#[derive(Rand, Debug)]
+struct Fields {
+    a: u8,
+    #[rand(range = "-5..5")]
+    b: i32,
+    #[rand(range = "1..=6")]
+    c: u16,
+    #[rand(distr = "Normal::new(10.0, 0.1)")]
+    d: f64,
+    #[rand(distr = "Exp::new(2.0)")]
+    e: f64,
+    f: (bool, Option<char>, [u8; 4]),
+    #[rand(range = "0..=255")]
+    g: u8,
+}
+
+#[derive(Rand, Debug, PartialEq)]
+struct Generic<T> {
+    x: T,
+    y: Option<T>,
+}
+
+#[derive(Rand, Debug, PartialEq)]
+enum Uniform {
+    A,
+    B(u64),
+    C { x: u64 },
+}
+
+#[allow(dead_code)]
+#[derive(Rand, Debug, PartialEq)]
+enum Weighted {
+    #[rand(weight = 0)]
+    Never,
+    #[rand(weight = 1)]
+    Rare,
+    #[rand(weight = 9)]
+    Common,
+}
+
+#[test]
+fn test_struct() {
+    let mut rng = thread_rng();
+    assert_eq!(rng.gen::<Unit>(), Unit);
+    for _ in 0..100 {
+        let x: Fields = rng.gen();
+        assert!(-5 <= x.b && x.b < 5);
+        assert!(1 <= x.c && x.c <= 6);
+        assert!(9.0 < x.d && x.d < 11.0);
+        assert!(x.e >= 0.0);
+        let (_, _, _, _) = (x.a, x.f.0, x.f.2, x.g);
+    }
+}
+
+#[test]
+fn test_field_order() {
+    let mut rng = StepRng::new(7, 1);
+    assert_eq!(rng.gen::<Tuple>(), Tuple(7, 8));
+    assert_eq!(rng.gen::<Generic<u64>>().x, 9);
+    let _: Generic<[i16; 3]> = rng.gen();
+}
+
+#[test]
+fn test_enum() {
+    let mut rng = thread_rng();
+    let mut counts = [0; 3];
+    for _ in 0..3000 {
+        match rng.gen() {
+            Uniform::A => counts[0] += 1,
+            Uniform::B(_) => counts[1] += 1,
+            Uniform::C { .. } => counts[2] += 1,
+        }
+    }
+    for &c in counts.iter() {
+        assert!(c > 800 && c < 1200, "{:?}", counts);
+    }
+}
+
+#[test]
+fn test_enum_weights() {
+    let mut rng = thread_rng();
+    let mut rare = 0;
+    for _ in 0..1000 {
+        match rng.gen() {
+            Weighted::Never => panic!("variant of weight 0 generated"),
+            Weighted::Rare => rare += 1,
+            Weighted::Common => {}
+        }
+    }
+    assert!(rare > 50 && rare < 150, "{}", rare);
+}
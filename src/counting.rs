@@ -0,0 +1,333 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper around another RNG that counts how much output is consumed.
+
+#[cfg(debug_assertions)]
+use core::fmt;
+use core::ops::Sub;
+
+use {Rng, Error};
+
+/// The number of calls made on a `CountingRng` and bytes requested, per method.
+///
+/// Snapshots can be subtracted to find the output consumed between them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RngCounts {
+    /// Number of calls to `next_u32`
+    pub next_u32: u64,
+    /// Number of calls to `next_u64`
+    pub next_u64: u64,
+    /// Number of calls to `next_u128`
+    #[cfg(feature = "i128_support")]
+    pub next_u128: u64,
+    /// Number of calls to `fill_bytes`
+    pub fill_bytes: u64,
+    /// Total length of the buffers passed to `fill_bytes`
+    pub fill_bytes_len: u64,
+    /// Number of calls to `try_fill`
+    pub try_fill: u64,
+    /// Total length of the buffers passed to `try_fill`
+    pub try_fill_len: u64,
+}
+
+impl RngCounts {
+    /// The total number of calls, over all methods.
+    pub fn calls(&self) -> u64 {
+        self.next_u32 + self.next_u64 + self.next_u128_calls() +
+            self.fill_bytes + self.try_fill
+    }
+
+    /// The total number of bytes of output requested, over all methods.
+    pub fn bytes(&self) -> u64 {
+        4 * self.next_u32 + 8 * self.next_u64 + 16 * self.next_u128_calls() +
+            self.fill_bytes_len + self.try_fill_len
+    }
+
+    #[cfg(feature = "i128_support")]
+    fn next_u128_calls(&self) -> u64 { self.next_u128 }
+    #[cfg(not(feature = "i128_support"))]
+    fn next_u128_calls(&self) -> u64 { 0 }
+}
+
+impl Sub for RngCounts {
+    type Output = RngCounts;
+
+    fn sub(self, rhs: RngCounts) -> RngCounts {
+        RngCounts {
+            next_u32: self.next_u32 - rhs.next_u32,
+            next_u64: self.next_u64 - rhs.next_u64,
+            #[cfg(feature = "i128_support")]
+            next_u128: self.next_u128 - rhs.next_u128,
+            fill_bytes: self.fill_bytes - rhs.fill_bytes,
+            fill_bytes_len: self.fill_bytes_len - rhs.fill_bytes_len,
+            try_fill: self.try_fill - rhs.try_fill,
+            try_fill_len: self.try_fill_len - rhs.try_fill_len,
+        }
+    }
+}
+
+/// Number of buckets in a `FillHistogram`: one per possible bit-length of a
+/// `usize`, plus one for zero.
+#[cfg(debug_assertions)]
+const HISTOGRAM_BUCKETS: usize = 8 * ::core::mem::size_of::<usize>() + 1;
+
+/// A histogram of the buffer lengths passed to `fill_bytes` and `try_fill`.
+///
+/// Lengths are grouped in power-of-two buckets: bucket `0` counts empty
+/// buffers, and bucket `k > 0` counts lengths in `[2^(k-1), 2^k)`.
+///
+/// Only available in debug builds.
+#[cfg(debug_assertions)]
+#[derive(Clone)]
+pub struct FillHistogram {
+    buckets: [u64; HISTOGRAM_BUCKETS],
+}
+
+#[cfg(debug_assertions)]
+impl FillHistogram {
+    fn new() -> FillHistogram {
+        FillHistogram { buckets: [0; HISTOGRAM_BUCKETS] }
+    }
+
+    fn record(&mut self, len: usize) {
+        let bits = 8 * ::core::mem::size_of::<usize>() - len.leading_zeros() as usize;
+        self.buckets[bits] += 1;
+    }
+
+    /// The count of each bucket, indexed by the bit-length of the buffer
+    /// length.
+    pub fn buckets(&self) -> &[u64] {
+        &self.buckets[..]
+    }
+}
+
+#[cfg(debug_assertions)]
+impl fmt::Debug for FillHistogram {
+    // Only print the non-empty buckets, as `lower..=upper: count`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FillHistogram {{")?;
+        let mut first = true;
+        for (k, &count) in self.buckets.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let (lower, upper) = if k == 0 {
+                (0, 0)
+            } else {
+                let lower = 1u64 << (k - 1);
+                (lower, lower - 1 + lower)
+            };
+            write!(f, "{} {}..={}: {}", if first { "" } else { "," },
+                   lower, upper, count)?;
+            first = false;
+        }
+        write!(f, " }}")
+    }
+}
+
+/// A wrapper around any RNG which counts calls and bytes requested per method.
+///
+/// Use `counts` to take a snapshot of the counters, and `reset` to zero them.
+/// In debug builds a histogram of the buffer lengths passed to `fill_bytes`
+/// and `try_fill` is also kept; see `fill_histogram`.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::{Sample, NewSeeded};
+/// use rand::prng::XorShiftRng;
+/// use rand::counting::CountingRng;
+///
+/// let mut rng = CountingRng::new(XorShiftRng::new().unwrap());
+/// let _: u64 = rng.gen();
+/// let _ = rng.gen_range(0..10);
+///
+/// let counts = rng.counts();
+/// assert_eq!(counts.next_u64, 1);
+/// assert!(counts.next_u32 >= 1);
+/// ```
+#[derive(Debug)]
+pub struct CountingRng<R> {
+    rng: R,
+    counts: RngCounts,
+    #[cfg(debug_assertions)]
+    histogram: FillHistogram,
+}
+
+impl<R: Rng> CountingRng<R> {
+    /// Create a new `CountingRng` wrapping `rng`, with all counts zero.
+    pub fn new(rng: R) -> CountingRng<R> {
+        CountingRng {
+            rng,
+            counts: RngCounts::default(),
+            #[cfg(debug_assertions)]
+            histogram: FillHistogram::new(),
+        }
+    }
+
+    /// Take a snapshot of the counts.
+    pub fn counts(&self) -> RngCounts {
+        self.counts
+    }
+
+    /// The histogram of buffer lengths passed to `fill_bytes` and
+    /// `try_fill`. Only available in debug builds.
+    #[cfg(debug_assertions)]
+    pub fn fill_histogram(&self) -> &FillHistogram {
+        &self.histogram
+    }
+
+    /// Reset all counts (and the histogram) to zero.
+    pub fn reset(&mut self) {
+        self.counts = RngCounts::default();
+        #[cfg(debug_assertions)]
+        {
+            self.histogram = FillHistogram::new();
+        }
+    }
+
+    /// Consume self, returning the wrapped RNG.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    #[cfg(debug_assertions)]
+    fn record_fill(&mut self, len: usize) {
+        self.histogram.record(len);
+    }
+    #[cfg(not(debug_assertions))]
+    fn record_fill(&mut self, _len: usize) {}
+}
+
+impl<R: Rng> Rng for CountingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.counts.next_u32 += 1;
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.counts.next_u64 += 1;
+        self.rng.next_u64()
+    }
+
+    #[cfg(feature = "i128_support")]
+    fn next_u128(&mut self) -> u128 {
+        self.counts.next_u128 += 1;
+        self.rng.next_u128()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.counts.fill_bytes += 1;
+        self.counts.fill_bytes_len += dest.len() as u64;
+        self.record_fill(dest.len());
+        self.rng.fill_bytes(dest);
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.counts.try_fill += 1;
+        self.counts.try_fill_len += dest.len() as u64;
+        self.record_fill(dest.len());
+        self.rng.try_fill(dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {Rng, Sample};
+    use distributions::Range;
+    use mock::StepRng;
+    use sequences::Shuffle;
+    use super::{CountingRng, RngCounts};
+
+    #[test]
+    fn test_counts() {
+        let mut rng = CountingRng::new(StepRng::new(0, 1));
+        rng.next_u32();
+        rng.next_u64();
+        rng.next_u64();
+        rng.fill_bytes(&mut [0u8; 5]);
+        rng.try_fill(&mut [0u8; 3]).unwrap();
+
+        let counts = rng.counts();
+        assert_eq!(counts, RngCounts {
+            next_u32: 1,
+            next_u64: 2,
+            #[cfg(feature = "i128_support")]
+            next_u128: 0,
+            fill_bytes: 1,
+            fill_bytes_len: 5,
+            try_fill: 1,
+            try_fill_len: 3,
+        });
+        assert_eq!(counts.calls(), 5);
+        assert_eq!(counts.bytes(), 4 + 16 + 5 + 3);
+
+        rng.reset();
+        assert_eq!(rng.counts(), RngCounts::default());
+    }
+
+    #[test]
+    fn test_snapshot_difference() {
+        let mut rng = CountingRng::new(StepRng::new(0, 1));
+        rng.next_u32();
+        let before = rng.counts();
+        rng.next_u32();
+        rng.next_u64();
+        let diff = rng.counts() - before;
+        assert_eq!(diff.next_u32, 1);
+        assert_eq!(diff.next_u64, 1);
+        assert_eq!(diff.calls(), 2);
+    }
+
+    #[test]
+    fn test_transparent() {
+        // the wrapper must not change the output
+        let mut a = StepRng::new(3, 0x9e37_79b9_7f4a_7c15);
+        let mut b = CountingRng::new(StepRng::new(3, 0x9e37_79b9_7f4a_7c15));
+        let range = Range::new(-7, 1000);
+        let (mut x, mut y) = ([1, 2, 3, 4, 5, 6], [1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(a.gen::<u64>(), b.gen::<u64>());
+        assert_eq!(a.sample(range), b.sample(range));
+        x.shuffle(&mut a);
+        y.shuffle(&mut b);
+        assert_eq!(x, y);
+
+        // gen::<u64>() uses next_u64; range sampling and shuffling of i32 and
+        // usize values use next_u32 or next_u64 depending on the type
+        let counts = b.counts();
+        assert!(counts.next_u64 >= 1);
+        assert!(counts.calls() >= 1 + 1 + 5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_fill_histogram() {
+        let mut rng = CountingRng::new(StepRng::new(0, 1));
+        let mut buf = [0u8; 100];
+        rng.fill_bytes(&mut buf[..0]);
+        rng.fill_bytes(&mut buf[..1]);
+        rng.fill_bytes(&mut buf[..5]);
+        rng.fill_bytes(&mut buf[..7]);
+        rng.try_fill(&mut buf[..64]).unwrap();
+
+        {
+            let buckets = rng.fill_histogram().buckets();
+            assert_eq!(&buckets[..8], &[1, 1, 0, 2, 0, 0, 0, 1]);
+            assert_eq!(buckets.iter().sum::<u64>(), 5);
+            assert_eq!(format!("{:?}", rng.fill_histogram()),
+                       "FillHistogram { 0..=0: 1, 1..=1: 1, 4..=7: 2, 64..=127: 1 }");
+        }
+
+        rng.reset();
+        assert_eq!(rng.fill_histogram().buckets().iter().sum::<u64>(), 0);
+    }
+}
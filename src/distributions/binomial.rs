@@ -0,0 +1,339 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The binomial distribution.
+
+use Rng;
+use distributions::{Distribution, Uniform01, Open01, Rand};
+
+/// The binomial distribution `Binomial(n, p)`.
+///
+/// This distribution has probability mass function:
+/// `f(k) = n!/(k!(n-k)!) p^k (1-p)^(n-k)` for `k >= 0`.
+///
+/// If `n*p` (or `n*(1-p)` if that is smaller) is below 10, samples are
+/// generated by inversion; otherwise the BTPE algorithm[1] is used. Both are
+/// exact.
+///
+/// [1]: Voratas Kachitvichyanukul and Bruce W. Schmeiser (1988).
+/// [*Binomial random variate
+/// generation*](https://dl.acm.org/citation.cfm?id=42381). Communications of
+/// the ACM 31, 2, 216-222.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Binomial, Distribution};
+///
+/// let bin = Binomial::new(20, 0.3);
+/// let v = bin.sample(&mut rand::thread_rng());
+/// println!("{} is from a binomial distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Binomial {
+    /// Number of trials.
+    n: u64,
+    /// Probability of success.
+    p: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Binomial` distribution.
+    pub enum BinomialError {
+        /// `p` is NaN or outside `[0, 1]`.
+        ProbabilityOutOfRange => "`p` is not in [0, 1]",
+    }
+}
+
+impl Binomial {
+    /// Construct a new `Binomial` with the given number of trials `n` and
+    /// probability of success `p`. Panics if `p` is NaN, `p < 0` or
+    /// `p > 1`; see `try_new`.
+    pub fn new(n: u64, p: f64) -> Binomial {
+        Binomial::try_new(n, p).unwrap_or_else(|e| panic!("Binomial::new: {}", e))
+    }
+
+    /// Construct a new `Binomial` with the given number of trials `n` and
+    /// probability of success `p`, or return `ProbabilityOutOfRange` if `p`
+    /// is NaN or not in `[0, 1]`.
+    pub fn try_new(n: u64, p: f64) -> Result<Binomial, BinomialError> {
+        if !(p >= 0.0 && p <= 1.0) {
+            return Err(BinomialError::ProbabilityOutOfRange);
+        }
+        Ok(Binomial { n, p })
+    }
+}
+
+/// Below this value of `n * p` inversion is used rather than BTPE.
+const BINV_THRESHOLD: f64 = 10.;
+
+/// The largest value considered by inversion before starting over. With
+/// `n * p < BINV_THRESHOLD` the probability of exceeding this is negligible,
+/// but rounding errors could otherwise make the search run off to `n`.
+const BINV_MAX_X: u64 = 110;
+
+/// Sample by inversion: walk up the PMF from zero until it exceeds a uniform
+/// variate. The expected number of steps is about `n * p`.
+fn binv<R: Rng+?Sized>(rng: &mut R, n: u64, p: f64) -> u64 {
+    let q = 1. - p;
+    let s = p / q;
+    let a = (n as f64 + 1.) * s;
+    // q^n, accurate even for large n and small p
+    let r0 = ((n as f64) * (-p).ln_1p()).exp();
+
+    let mut r = r0;
+    let mut u = f64::rand(rng, Uniform01);
+    let mut x = 0;
+    while u > r {
+        u -= r;
+        x += 1;
+        if x > BINV_MAX_X {
+            x = 0;
+            r = r0;
+            u = f64::rand(rng, Uniform01);
+        } else {
+            r *= a / (x as f64) - s;
+        }
+    }
+    x
+}
+
+/// Sample with the BTPE algorithm ("Binomial, Triangle, Parallelogram,
+/// Exponential"): rejection from a majorizing function made of a triangle,
+/// two parallelograms and two exponential tails. Requires `p <= 0.5`.
+fn btpe<R: Rng+?Sized>(rng: &mut R, n: u64, p: f64) -> u64 {
+    // Beyond this distance from the mode the acceptance test uses the
+    // squeeze of step 5.2 rather than evaluating the PMF ratio directly.
+    const SQUEEZE_THRESHOLD: i64 = 20;
+
+    // Step 0: set up constants
+    let q = 1. - p;
+    let n_f = n as f64;
+    let npq = n_f * p * q;
+    let f_m = n_f * p + p;
+    let m = f_m as i64;
+    // radius of the triangle region
+    let p1 = (2.195 * npq.sqrt() - 4.6 * q).floor() + 0.5;
+    let x_m = m as f64 + 0.5;
+    let x_l = x_m - p1;
+    let x_r = x_m + p1;
+    let c = 0.134 + 20.5 / (15.3 + m as f64);
+    // parameters of the exponential tails
+    let lambda = |a: f64| a * (1. + 0.5 * a);
+    let lambda_l = lambda((f_m - x_l) / (f_m - x_l * p));
+    let lambda_r = lambda((x_r - f_m) / (x_r * q));
+    // cumulative areas of the regions
+    let p2 = p1 * (1. + 2. * c);
+    let p3 = p2 + c / lambda_l;
+    let p4 = p3 + c / lambda_r;
+
+    loop {
+        // Step 1: choose a region; accept immediately in the triangle
+        let u = p4 * f64::rand(rng, Uniform01);
+        let mut v = f64::rand(rng, Open01);
+        let y;
+        if u <= p1 {
+            return (x_m - p1 * v + u) as u64;
+        } else if u <= p2 {
+            // Step 2: parallelograms
+            let x = x_l + (u - p1) / c;
+            v = v * c + 1. - (x - x_m).abs() / p1;
+            if v > 1. {
+                continue;
+            }
+            y = x as i64;
+        } else if u <= p3 {
+            // Step 3: left exponential tail
+            let x = x_l + v.ln() / lambda_l;
+            if x < 0. {
+                continue;
+            }
+            y = x as i64;
+            v *= (u - p2) * lambda_l;
+        } else {
+            // Step 4: right exponential tail
+            let x = x_r - v.ln() / lambda_r;
+            if x > n_f + 1. {
+                continue;
+            }
+            y = x as i64;
+            if y as u64 > n {
+                continue;
+            }
+            v *= (u - p3) * lambda_r;
+        }
+
+        // Step 5: acceptance/rejection test
+        let k = (y - m).abs();
+        if k <= SQUEEZE_THRESHOLD || k as f64 >= 0.5 * npq - 1. {
+            // Step 5.1: evaluate f(y)/f(m) recursively
+            let s = p / q;
+            let a = s * (n_f + 1.);
+            let mut f = 1.;
+            if m < y {
+                for i in (m + 1)..(y + 1) {
+                    f *= a / (i as f64) - s;
+                }
+            } else if m > y {
+                for i in (y + 1)..(m + 1) {
+                    f /= a / (i as f64) - s;
+                }
+            }
+            if v <= f {
+                return y as u64;
+            }
+            continue;
+        }
+
+        // Step 5.2: squeeze using upper and lower bounds on ln(f(y)/f(m))
+        let k = k as f64;
+        let rho = (k / npq) * ((k * (k / 3. + 0.625) + 1. / 6.) / npq + 0.5);
+        let t = -0.5 * k * k / npq;
+        let alpha = v.ln();
+        if alpha < t - rho {
+            return y as u64;
+        }
+        if alpha > t + rho {
+            continue;
+        }
+
+        // Step 5.3: final test against ln(f(y)/f(m)), using Stirling's
+        // formula with correction terms
+        let x1 = (y + 1) as f64;
+        let f1 = (m + 1) as f64;
+        let z = n_f + 1. - m as f64;
+        let w = n_f - y as f64 + 1.;
+
+        fn stirling(a: f64) -> f64 {
+            let a2 = a * a;
+            (13860. - (462. - (132. - (99. - 140. / a2) / a2) / a2) / a2) / a / 166320.
+        }
+
+        // The signs of the correction terms follow the GSL implementation,
+        // which corrects a misprint in the paper.
+        let bound = x_m * (f1 / x1).ln()
+            + (n_f - m as f64 + 0.5) * (z / w).ln()
+            + ((y - m) as f64) * (w * p / (x1 * q)).ln()
+            + stirling(f1) + stirling(z) - stirling(x1) - stirling(w);
+        if alpha <= bound {
+            return y as u64;
+        }
+    }
+}
+
+impl Distribution<u64> for Binomial {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        // Handle the trivial cases, which the algorithms below do not.
+        if self.p == 0.0 {
+            return 0;
+        } else if self.p == 1.0 {
+            return self.n;
+        }
+
+        // Both algorithms require p <= 0.5; use the symmetry
+        // Binomial(n, p) = n - Binomial(n, 1 - p) otherwise.
+        let flipped = self.p > 0.5;
+        let p = if flipped { 1. - self.p } else { self.p };
+
+        let x = if (self.n as f64) * p < BINV_THRESHOLD {
+            binv(rng, self.n, p)
+        } else {
+            btpe(rng, self.n, p)
+        };
+
+        if flipped { self.n - x } else { x }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use SeedableRng;
+    use distributions::Distribution;
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{Binomial, BinomialError};
+
+    /// The PMF of `Binomial(n, p)` on `0..=n`, computed by the recurrence
+    /// `f(k+1) = f(k) * (n-k)/(k+1) * p/(1-p)` in log space.
+    fn pmf(n: u64, p: f64) -> Vec<f64> {
+        let (ln_p, ln_q) = (p.ln(), (-p).ln_1p());
+        let mut ln_f = n as f64 * ln_q;
+        let mut result = vec![ln_f.exp()];
+        for k in 0..n {
+            ln_f += ((n - k) as f64).ln() - ((k + 1) as f64).ln() + ln_p - ln_q;
+            result.push(ln_f.exp());
+        }
+        result
+    }
+
+    fn test_fit(n: u64, p: f64) {
+        let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, n as u32]);
+        let binomial = Binomial::new(n, p);
+        let samples: Vec<u64> = (0..20_000).map(|_| binomial.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x <= n));
+        let pmf = pmf(n, p);
+        assert_discrete_fit(&samples, |k| pmf[k as usize]);
+    }
+
+    #[test]
+    fn test_binomial_inversion() {
+        test_fit(1, 0.3);
+        test_fit(10, 0.5);
+        test_fit(100, 0.05);
+        test_fit(1_000_000, 1e-6);
+        test_fit(40, 0.9);
+    }
+
+    #[test]
+    fn test_binomial_btpe() {
+        test_fit(20, 0.5);
+        test_fit(100, 0.3);
+        test_fit(1000, 0.01);
+        test_fit(2000, 0.7);
+        test_fit(100_000, 0.45);
+    }
+
+    #[test]
+    fn test_binomial_mean() {
+        let mut rng = ::test::rng();
+        let binomial = Binomial::new(1 << 40, 0.25);
+        let total: f64 = (0..1000).map(|_| binomial.sample(&mut rng) as f64).sum();
+        let mean = total / 1000.;
+        // the standard deviation of the mean is about 14,400
+        assert!((mean - (1u64 << 38) as f64).abs() < 75_000., "{}", mean);
+    }
+
+    #[test]
+    fn test_binomial_end_points() {
+        let mut rng = ::test::rng();
+        assert_eq!(Binomial::new(20, 0.0).sample(&mut rng), 0);
+        assert_eq!(Binomial::new(20, 1.0).sample(&mut rng), 20);
+        assert_eq!(Binomial::new(0, 0.5).sample(&mut rng), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_binomial_invalid_p_neg() {
+        Binomial::new(20, -0.1);
+    }
+    #[test]
+    #[should_panic]
+    fn test_binomial_invalid_p_large() {
+        Binomial::new(20, 1.1);
+    }
+    #[test]
+    fn test_binomial_try_new() {
+        assert!(Binomial::try_new(20, 0.0).is_ok());
+        for &p in [-0.1, 1.1, f64::NAN, f64::NEG_INFINITY].iter() {
+            assert_eq!(Binomial::try_new(20, p).unwrap_err(), BinomialError::ProbabilityOutOfRange);
+        }
+    }
+}
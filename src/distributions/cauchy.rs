@@ -0,0 +1,117 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Cauchy distribution.
+
+use std::f64::consts::PI;
+
+use Rng;
+use distributions::{Distribution, Open01, Rand};
+
+/// The Cauchy distribution `Cauchy(median, scale)`.
+///
+/// This distribution has density function:
+/// `f(x) = 1 / (pi * scale * (1 + ((x - median) / scale)^2))`.
+///
+/// It is heavy-tailed: neither its mean nor its variance is defined.
+/// Samples are generated by inversion of the CDF.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Cauchy, Distribution};
+///
+/// let cau = Cauchy::new(2.0, 5.0);
+/// let v = cau.sample(&mut rand::thread_rng());
+/// println!("{} is from a Cauchy(2, 5) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Cauchy {
+    median: f64,
+    scale: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Cauchy` distribution.
+    pub enum CauchyError {
+        /// `median` is NaN or infinite.
+        MedianNotFinite => "`median` is NaN or infinite",
+        /// `scale` is NaN or infinite.
+        ScaleNotFinite => "`scale` is NaN or infinite",
+        /// `scale <= 0`.
+        ScaleNotPositive => "`scale` is not positive",
+    }
+}
+
+impl Cauchy {
+    /// Construct a new `Cauchy` with the given shape parameters
+    /// `median` the peak location and `scale` the scale factor.
+    /// Panics if `median` is not finite or `scale` is not finite and
+    /// positive; see `try_new`.
+    pub fn new(median: f64, scale: f64) -> Cauchy {
+        Cauchy::try_new(median, scale).unwrap_or_else(|e| panic!("Cauchy::new: {}", e))
+    }
+
+    /// Construct a new `Cauchy`, or return an error if the parameters are
+    /// invalid: `MedianNotFinite` if `median` is NaN or infinite,
+    /// `ScaleNotFinite` if `scale` is, and `ScaleNotPositive` if
+    /// `scale <= 0`.
+    pub fn try_new(median: f64, scale: f64) -> Result<Cauchy, CauchyError> {
+        if !median.is_finite() {
+            return Err(CauchyError::MedianNotFinite);
+        } else if !scale.is_finite() {
+            return Err(CauchyError::ScaleNotFinite);
+        } else if scale <= 0.0 {
+            return Err(CauchyError::ScaleNotPositive);
+        }
+        Ok(Cauchy { median, scale })
+    }
+}
+
+impl Distribution<f64> for Cauchy {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+        let x = f64::rand(rng, Open01);
+        self.median + self.scale * (PI * (x - 0.5)).tan()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+    use std::f64;
+    use distributions::test::assert_quantiles;
+    use super::{Cauchy, CauchyError};
+
+    #[test]
+    fn test_cauchy_quantiles() {
+        for &(median, scale) in [(0.0, 1.0), (2.0, 5.0), (-50.0, 0.01)].iter() {
+            assert_quantiles(Cauchy::new(median, scale),
+                             |p| median + scale * (PI * (p - 0.5)).tan());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cauchy_invalid_scale_zero() {
+        Cauchy::new(0.0, 0.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_cauchy_invalid_scale_neg() {
+        Cauchy::new(0.0, -10.0);
+    }
+    #[test]
+    fn test_cauchy_try_new() {
+        assert_eq!(Cauchy::try_new(f64::NAN, 1.0).unwrap_err(), CauchyError::MedianNotFinite);
+        assert_eq!(Cauchy::try_new(0.0, f64::INFINITY).unwrap_err(),
+                   CauchyError::ScaleNotFinite);
+        assert_eq!(Cauchy::try_new(0.0, 0.0).unwrap_err(), CauchyError::ScaleNotPositive);
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Distributions over the characters of an alphabet, for generating random
+//! strings with `Sample::gen_string`.
+
+#[cfg(feature="std")]
+use std::vec::Vec;
+
+use Rng;
+use distributions::Distribution;
+#[cfg(feature="std")]
+use distributions::range::{Range, RangeInt};
+
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                              abcdefghijklmnopqrstuvwxyz\
+                              0123456789";
+const HEX_DIGITS: &[u8] = b"0123456789abcdef";
+const BASE64_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                              abcdefghijklmnopqrstuvwxyz\
+                              0123456789+/";
+
+/// Sample ASCII letters and digits (`A-Z`, `a-z` and `0-9`) uniformly.
+///
+/// This samples the same characters as `AsciiWordChar`, but is faster, as
+/// it takes 6 random bits per character.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::{thread_rng, Sample};
+/// use rand::distributions::Alphanumeric;
+///
+/// let password = thread_rng().gen_string(Alphanumeric, 16);
+/// assert_eq!(password.len(), 16);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Alphanumeric;
+
+/// Sample lower case hexadecimal digits (`0-9` and `a-f`) uniformly.
+#[derive(Clone, Copy, Debug)]
+pub struct HexDigit;
+
+/// Sample the characters of the standard base64 alphabet (`A-Z`, `a-z`,
+/// `0-9`, `+` and `/`) uniformly.
+#[derive(Clone, Copy, Debug)]
+pub struct Base64Char;
+
+impl Distribution<u8> for Alphanumeric {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u8 {
+        // Take the 6 most significant bits, and reject the two values beyond
+        // the 62 characters.
+        loop {
+            let i = (rng.next_u32() >> (32 - 6)) as usize;
+            if i < ALPHANUMERIC.len() {
+                return ALPHANUMERIC[i];
+            }
+        }
+    }
+}
+
+impl Distribution<u8> for HexDigit {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u8 {
+        HEX_DIGITS[(rng.next_u32() >> (32 - 4)) as usize]
+    }
+}
+
+impl Distribution<u8> for Base64Char {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u8 {
+        BASE64_CHARS[(rng.next_u32() >> (32 - 6)) as usize]
+    }
+}
+
+macro_rules! char_via_u8 {
+    ($distr:ident) => {
+        impl Distribution<char> for $distr {
+            #[inline]
+            fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> char {
+                let b: u8 = self.sample(rng);
+                b as char
+            }
+        }
+    }
+}
+
+char_via_u8! { Alphanumeric }
+char_via_u8! { HexDigit }
+char_via_u8! { Base64Char }
+
+/// Sample the characters of a given alphabet uniformly.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::{thread_rng, Sample};
+/// use rand::distributions::Charset;
+///
+/// // without characters that are easily confused
+/// let charset = Charset::new("ABCDEFGHJKLMNPQRSTUVWXYZ23456789");
+/// let code = thread_rng().gen_string(&charset, 8);
+/// assert_eq!(code.len(), 8);
+/// ```
+#[cfg(feature="std")]
+#[derive(Clone, Debug)]
+pub struct Charset {
+    repr: CharsetRepr,
+    range: Range<RangeInt<usize>>,
+}
+
+// Store ASCII alphabets as bytes, which are both smaller and faster to look
+// up.
+#[cfg(feature="std")]
+#[derive(Clone, Debug)]
+enum CharsetRepr {
+    Bytes(Vec<u8>),
+    Chars(Vec<char>),
+}
+
+#[cfg(feature="std")]
+parameter_error! {
+    /// An error constructing a `Charset`.
+    pub enum CharsetError {
+        /// The alphabet has no characters.
+        Empty => "the alphabet is empty",
+        /// A character occurs more than once in the alphabet, which would
+        /// make it more likely than the others.
+        DuplicateChar => "the alphabet contains a character more than once",
+    }
+}
+
+#[cfg(feature="std")]
+impl Charset {
+    /// Create a `Charset` sampling the characters of `alphabet`. Panics if
+    /// `alphabet` is empty or has duplicate characters; see `try_new`.
+    pub fn new(alphabet: &str) -> Charset {
+        Charset::try_new(alphabet).unwrap_or_else(|e| panic!("Charset::new: {}", e))
+    }
+
+    /// Create a `Charset` sampling the characters of `alphabet`, or return
+    /// `Empty` if it has no characters and `DuplicateChar` if a character
+    /// occurs more than once.
+    pub fn try_new(alphabet: &str) -> Result<Charset, CharsetError> {
+        let mut chars: Vec<char> = alphabet.chars().collect();
+        if chars.is_empty() {
+            return Err(CharsetError::Empty);
+        }
+        let mut sorted = chars.clone();
+        sorted.sort();
+        if sorted.windows(2).any(|w| w[0] == w[1]) {
+            return Err(CharsetError::DuplicateChar);
+        }
+
+        let range = Range::new(0, chars.len());
+        let repr = if alphabet.is_ascii() {
+            CharsetRepr::Bytes(alphabet.as_bytes().to_vec())
+        } else {
+            chars.shrink_to_fit();
+            CharsetRepr::Chars(chars)
+        };
+        Ok(Charset { repr, range })
+    }
+
+    /// The number of characters in the alphabet.
+    pub fn len(&self) -> usize {
+        match self.repr {
+            CharsetRepr::Bytes(ref bytes) => bytes.len(),
+            CharsetRepr::Chars(ref chars) => chars.len(),
+        }
+    }
+
+    /// Always `false`: an alphabet can not be empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(feature="std")]
+impl Distribution<char> for Charset {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> char {
+        let i = self.range.sample(rng);
+        match self.repr {
+            CharsetRepr::Bytes(ref bytes) => bytes[i] as char,
+            CharsetRepr::Chars(ref chars) => chars[i],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Sample;
+    use distributions::Distribution;
+    use mock::ConstRng;
+    use super::{Alphanumeric, HexDigit, Base64Char, Charset, CharsetError};
+
+    /// Check that `distr` samples exactly the characters of `alphabet`, with
+    /// roughly equal frequencies.
+    fn check_alphabet<D: Distribution<char>>(distr: D, alphabet: &str) {
+        let mut rng = ::test::rng();
+        let chars: Vec<char> = alphabet.chars().collect();
+        let n = 200 * chars.len();
+        let mut counts = vec![0; chars.len()];
+        for c in rng.gen_string(&distr, n).chars() {
+            match chars.iter().position(|&x| x == c) {
+                Some(i) => counts[i] += 1,
+                None => panic!("{:?} is not in {:?}", c, alphabet),
+            }
+        }
+        // The expected count is 200, with a standard deviation of at most
+        // about 14.
+        assert!(counts.iter().all(|&k| k > 120 && k < 280), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_alphanumeric() {
+        check_alphabet(Alphanumeric,
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789");
+        let b: u8 = ConstRng::new(0).sample(Alphanumeric);
+        assert_eq!(b, b'A');
+        let c: char = ConstRng::new(61 << 26).sample(Alphanumeric);
+        assert_eq!(c, '9');
+    }
+
+    #[test]
+    fn test_hex_digit() {
+        check_alphabet(HexDigit, "0123456789abcdef");
+        let c: char = ConstRng::new(!0).sample(HexDigit);
+        assert_eq!(c, 'f');
+    }
+
+    #[test]
+    fn test_base64_char() {
+        check_alphabet(Base64Char,
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+        let c: char = ConstRng::new(!0).sample(Base64Char);
+        assert_eq!(c, '/');
+    }
+
+    #[test]
+    fn test_charset() {
+        check_alphabet(Charset::new("xyz"), "xyz");
+        check_alphabet(Charset::new("äöü-ß"), "äöü-ß");
+        check_alphabet(Charset::new("0"), "0");
+        assert_eq!(Charset::new("äöü-ß").len(), 5);
+
+        assert_eq!(Charset::try_new("").unwrap_err(), CharsetError::Empty);
+        assert_eq!(Charset::try_new("abca").unwrap_err(), CharsetError::DuplicateChar);
+        assert_eq!(Charset::try_new("ßaß").unwrap_err(), CharsetError::DuplicateChar);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_charset_empty() {
+        Charset::new("");
+    }
+
+    #[test]
+    fn test_gen_string() {
+        let mut rng = ::test::rng();
+        assert_eq!(rng.gen_string(HexDigit, 0), "");
+        let s = rng.gen_string(Charset::new("ä"), 3);
+        assert_eq!(s, "äää");
+        assert_eq!(s.len(), 6);
+    }
+}
@@ -10,7 +10,7 @@
 
 //! Generic value creation
 
-use Rng;
+use {Rng, Sample};
 use distributions::{Distribution, Rand};
 use distributions::uniform::{Uniform, Uniform01, codepoint};
 
@@ -19,23 +19,31 @@ use distributions::uniform::{Uniform, Uniform01, codepoint};
 /// 
 /// Makes use of the following distributions:
 /// 
-/// *   [`Uniform`] for integer types
+/// *   [`Uniform`] for integer types and `bool`
 /// *   [`Uniform01`] for floating point types
 /// 
 /// Makes use of the following methods:
 /// 
 /// *   [`codepoint`] for `char`
 /// 
+/// Composite types are generated element-wise with `Default`: this covers
+/// tuples of up to 12 elements, arrays of up to 32 elements, `Option<T>`
+/// (which is `None` with probability 1/2) and `Wrapping<T>`.
+/// 
 /// TODO: link
 #[derive(Debug)]
 pub struct Default;
 
 // ----- implementations -----
 
-impl<T: Rand<Uniform>> Distribution<T> for Default {
+impl<T> Distribution<T> for Default where Uniform: Distribution<T> {
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> T {
         T::rand(rng, Uniform)
     }
+
+    fn fill<R: Rng+?Sized>(&self, rng: &mut R, dest: &mut [T]) {
+        Uniform.fill(rng, dest)
+    }
 }
 
 // FIXME: https://github.com/rust-lang/rust/issues/23341
@@ -62,11 +70,86 @@ impl Distribution<char> for Default {
     }
 }
 
+macro_rules! tuple_impl {
+    // use variables to indicate the arity of the tuple
+    ($($tyvar:ident),* ) => {
+        // the trailing commas are for the 1 tuple
+        impl< $( $tyvar ),* >
+            Distribution<( $( $tyvar ),* , )>
+            for Default
+            where $( Default: Distribution<$tyvar> ),*
+        {
+            #[inline]
+            fn sample<R: Rng+?Sized>(&self, _rng: &mut R) -> ( $( $tyvar ),* , ) {
+                (
+                    // use the $tyvar's to get the appropriate number of
+                    // repeats (they're not actually needed)
+                    $(
+                        _rng.gen::<$tyvar>()
+                    ),*
+                    ,
+                )
+            }
+        }
+    }
+}
+
+impl Distribution<()> for Default {
+    #[inline]
+    fn sample<R: Rng+?Sized>(&self, _: &mut R) {}
+}
+tuple_impl!{A}
+tuple_impl!{A, B}
+tuple_impl!{A, B, C}
+tuple_impl!{A, B, C, D}
+tuple_impl!{A, B, C, D, E}
+tuple_impl!{A, B, C, D, E, F}
+tuple_impl!{A, B, C, D, E, F, G}
+tuple_impl!{A, B, C, D, E, F, G, H}
+tuple_impl!{A, B, C, D, E, F, G, H, I}
+tuple_impl!{A, B, C, D, E, F, G, H, I, J}
+tuple_impl!{A, B, C, D, E, F, G, H, I, J, K}
+tuple_impl!{A, B, C, D, E, F, G, H, I, J, K, L}
+
+macro_rules! array_impl {
+    // recursive, given at least one type parameter:
+    {$n:expr, $t:ident, $($ts:ident,)*} => {
+        array_impl!{($n - 1), $($ts,)*}
+
+        impl<T> Distribution<[T; $n]> for Default where Default: Distribution<T> {
+            #[inline]
+            fn sample<R: Rng+?Sized>(&self, _rng: &mut R) -> [T; $n] {
+                [_rng.gen::<$t>(), $(_rng.gen::<$ts>()),*]
+            }
+        }
+    };
+    // empty case:
+    {$n:expr,} => {
+        impl<T> Distribution<[T; $n]> for Default {
+            fn sample<R: Rng+?Sized>(&self, _rng: &mut R) -> [T; $n] { [] }
+        }
+    };
+}
+
+array_impl!{32, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T,}
+
+impl<T> Distribution<Option<T>> for Default where Default: Distribution<T> {
+    #[inline]
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Option<T> {
+        if rng.gen::<bool>() {
+            Some(rng.gen())
+        } else {
+            None
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use {Rng, thread_rng};
-    use distributions::{Rand, Default};
+    use core::num::Wrapping;
+    use {Rng, Sample, thread_rng};
+    use distributions::{Rand, Default, Uniform};
+    use mock::StepRng;
     
     #[test]
     fn test_types() {
@@ -84,4 +167,33 @@ mod tests {
         do_test::<char>(rng);
         do_test::<bool>(rng);
     }
+
+    #[test]
+    fn test_composite() {
+        let mut rng = ::test::rng();
+        let _: () = rng.gen();
+        let _: (u8,) = rng.gen();
+        let _: (u8, f64) = rng.gen();
+        let _: (i32, u64, char, bool, f32, (u8, u16), [u8; 3]) = rng.gen();
+        let _: (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8) = rng.gen();
+        let _: [u32; 0] = rng.gen();
+        let _: [f64; 7] = rng.gen();
+        let _: [[u8; 2]; 32] = rng.gen();
+        let _: Option<(u16, [char; 4])> = rng.gen();
+        let _: Wrapping<i64> = rng.gen();
+        let _: Wrapping<u8> = rng.sample(Uniform);
+    }
+
+    #[test]
+    fn test_composite_values() {
+        // elements are generated in order, one sample each
+        let mut rng = StepRng::new(0, 1);
+        let x: (u64, u64, [u64; 3], Wrapping<u64>) = rng.gen();
+        assert_eq!(x, (0, 1, [2, 3, 4], Wrapping(5)));
+
+        let mut rng = ::test::rng();
+        let some = (0..1000).filter(|_| rng.gen::<Option<()>>().is_some())
+            .count();
+        assert!(some > 400 && some < 600);
+    }
 }
@@ -0,0 +1,203 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Dirichlet distribution.
+
+use Rng;
+use distributions::{Distribution, Gamma};
+
+/// The Dirichlet distribution `Dirichlet(alpha)`, a distribution over the
+/// points of the `(k - 1)`-simplex: vectors of `k` non-negative numbers
+/// which sum to 1.
+///
+/// This is sampled by normalising a vector of independent `Gamma(alpha_i, 1)`
+/// samples. With `k = 2` the first component follows the
+/// `Beta(alpha_0, alpha_1)` distribution.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Dirichlet, Distribution};
+///
+/// let dirichlet = Dirichlet::new(vec![1.0, 2.0, 3.0]);
+/// let v = dirichlet.sample(&mut rand::thread_rng());
+/// println!("{:?} is from a Dirichlet([1.0, 2.0, 3.0]) distribution", v);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dirichlet {
+    gammas: Vec<Gamma>,
+}
+
+parameter_error! {
+    /// An error constructing a `Dirichlet` distribution.
+    pub enum DirichletError {
+        /// Fewer than two concentration parameters were given.
+        TooFewParameters => "fewer than two concentration parameters",
+        /// A concentration parameter is NaN or infinite.
+        AlphaNotFinite => "a concentration parameter is NaN or infinite",
+        /// A concentration parameter is `<= 0`.
+        AlphaNotPositive => "a concentration parameter is not positive",
+    }
+}
+
+impl Dirichlet {
+    /// Construct a new `Dirichlet` with the given concentration parameters
+    /// `alpha`.
+    ///
+    /// Panics if `alpha` has fewer than two elements, or if any element is
+    /// not finite and positive; see `try_new`.
+    pub fn new(alpha: Vec<f64>) -> Dirichlet {
+        Dirichlet::try_new(alpha).unwrap_or_else(|e| panic!("Dirichlet::new: {}", e))
+    }
+
+    /// Construct a new `Dirichlet` with the given concentration parameters
+    /// `alpha`, or return an error if they are invalid.
+    ///
+    /// `alpha` must have at least two elements (`TooFewParameters`), each
+    /// finite (`AlphaNotFinite` for NaN or infinite values) and positive
+    /// (`AlphaNotPositive`).
+    pub fn try_new(alpha: Vec<f64>) -> Result<Dirichlet, DirichletError> {
+        if alpha.len() < 2 {
+            return Err(DirichletError::TooFewParameters);
+        }
+        for &a in alpha.iter() {
+            check_alpha(a)?;
+        }
+        Ok(Dirichlet {
+            gammas: alpha.iter().map(|&a| Gamma::new(a, 1.0)).collect(),
+        })
+    }
+
+    /// Construct a new symmetric `Dirichlet` of dimension `size`, with all
+    /// concentration parameters equal to `alpha`.
+    ///
+    /// Panics if `size < 2` or `alpha` is not finite and positive; see
+    /// `try_new_with_size`.
+    pub fn new_with_size(alpha: f64, size: usize) -> Dirichlet {
+        Dirichlet::try_new_with_size(alpha, size)
+            .unwrap_or_else(|e| panic!("Dirichlet::new_with_size: {}", e))
+    }
+
+    /// Construct a new symmetric `Dirichlet` of dimension `size`, or return
+    /// an error under the same conditions as `try_new`.
+    pub fn try_new_with_size(alpha: f64, size: usize)
+        -> Result<Dirichlet, DirichletError>
+    {
+        if size < 2 {
+            return Err(DirichletError::TooFewParameters);
+        }
+        check_alpha(alpha)?;
+        Ok(Dirichlet {
+            gammas: vec![Gamma::new(alpha, 1.0); size],
+        })
+    }
+}
+
+fn check_alpha(alpha: f64) -> Result<(), DirichletError> {
+    if !alpha.is_finite() {
+        Err(DirichletError::AlphaNotFinite)
+    } else if alpha <= 0.0 {
+        Err(DirichletError::AlphaNotPositive)
+    } else {
+        Ok(())
+    }
+}
+
+impl Distribution<Vec<f64>> for Dirichlet {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        loop {
+            let mut samples: Vec<f64> = self.gammas.iter().map(|g| g.sample(rng)).collect();
+            let sum: f64 = samples.iter().sum();
+            // With very small parameters all samples may underflow to zero,
+            // leaving the normalisation undefined.
+            if sum > 0.0 {
+                for x in samples.iter_mut() {
+                    *x /= sum;
+                }
+                return samples;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use distributions::Distribution;
+    use super::{Dirichlet, DirichletError};
+
+    fn test_moments(alpha: Vec<f64>) {
+        let mut rng = ::test::rng();
+        let dirichlet = Dirichlet::new(alpha.clone());
+        let n = 10_000;
+        let samples: Vec<Vec<f64>> = (0..n).map(|_| dirichlet.sample(&mut rng)).collect();
+        for x in samples.iter() {
+            assert_eq!(x.len(), alpha.len());
+            assert!(x.iter().all(|&x_i| x_i >= 0.0 && x_i <= 1.0));
+            assert!((x.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+
+        let alpha_0: f64 = alpha.iter().sum();
+        for (i, &a) in alpha.iter().enumerate() {
+            let mean = samples.iter().map(|x| x[i]).sum::<f64>() / n as f64;
+            let var = samples.iter().map(|x| (x[i] - mean) * (x[i] - mean)).sum::<f64>()
+                / (n - 1) as f64;
+            let expected_mean = a / alpha_0;
+            let expected_var = a * (alpha_0 - a) / (alpha_0 * alpha_0 * (alpha_0 + 1.));
+            // each component follows a Beta distribution on [0, 1]; see the
+            // Beta tests for the bound on the variance error
+            let err = 6. * (expected_var / n as f64).sqrt();
+            assert!((mean - expected_mean).abs() < err, "mean {} of component {}", mean, i);
+            assert!((var - expected_var).abs() < err, "variance {} of component {}", var, i);
+        }
+    }
+
+    #[test]
+    fn test_dirichlet_moments() {
+        test_moments(vec![1.0, 2.0, 3.0]);
+        test_moments(vec![0.1, 0.5, 10.0, 0.1]);
+        test_moments(vec![50.0, 50.0]);
+    }
+
+    #[test]
+    fn test_dirichlet_with_size() {
+        let mut rng = ::test::rng();
+        let dirichlet = Dirichlet::new_with_size(0.5, 5);
+        let x = dirichlet.sample(&mut rng);
+        assert_eq!(x.len(), 5);
+        assert!((x.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dirichlet_invalid_length() {
+        Dirichlet::new(vec![1.0]);
+    }
+    #[test]
+    #[should_panic]
+    fn test_dirichlet_invalid_alpha() {
+        Dirichlet::new(vec![1.0, 0.0, 1.0]);
+    }
+    #[test]
+    #[should_panic]
+    fn test_dirichlet_with_size_invalid() {
+        Dirichlet::new_with_size(1.0, 1);
+    }
+    #[test]
+    fn test_dirichlet_try_new() {
+        assert_eq!(Dirichlet::try_new(vec![]).unwrap_err(), DirichletError::TooFewParameters);
+        assert_eq!(Dirichlet::try_new(vec![1.0, f64::NAN]).unwrap_err(),
+                   DirichletError::AlphaNotFinite);
+        assert_eq!(Dirichlet::try_new(vec![1.0, -1.0]).unwrap_err(),
+                   DirichletError::AlphaNotPositive);
+        assert_eq!(Dirichlet::try_new_with_size(f64::INFINITY, 3).unwrap_err(),
+                   DirichletError::AlphaNotFinite);
+    }
+}
@@ -11,7 +11,7 @@
 //! The exponential distribution.
 
 use {Rng};
-use distributions::{ziggurat, ziggurat_tables, Distribution, Uniform01, Rand};
+use distributions::{ziggurat, Distribution, Float};
 
 /// Generates Exp(1) random numbers.
 ///
@@ -37,20 +37,27 @@ use distributions::{ziggurat, ziggurat_tables, Distribution, Uniform01, Rand};
 // This could be done via `-rng.gen::<f64>().ln()` but that is slower.
 #[inline]
 pub fn exp1<R: Rng+?Sized>(rng: &mut R) -> f64 {
+    exp1_float(rng)
+}
+
+/// Generates Exp(1) random numbers of any `Float` type; `exp1` is the `f64`
+/// version.
+#[inline]
+pub(crate) fn exp1_float<F: Float, R: Rng+?Sized>(rng: &mut R) -> F {
     #[inline]
-    fn pdf(x: f64) -> f64 {
+    fn pdf<F: Float>(x: F) -> F {
         (-x).exp()
     }
     #[inline]
-    fn zero_case<R:Rng+?Sized>(rng: &mut R, _u: f64) -> f64 {
-        let x = f64::rand(rng, Uniform01);
-        ziggurat_tables::ZIG_EXP_R - x.ln()
+    fn zero_case<F: Float, R:Rng+?Sized>(rng: &mut R, _u: F) -> F {
+        let x = F::uniform01(rng);
+        F::ZIG_EXP_R - x.ln()
     }
 
-    (ziggurat(rng, false,
-                    &ziggurat_tables::ZIG_EXP_X,
-                    &ziggurat_tables::ZIG_EXP_F,
-                    pdf, zero_case))
+    ziggurat(rng, false,
+             F::zig_exp_x(),
+             F::zig_exp_f(),
+             pdf, zero_case)
 }
 
 /// The exponential distribution `Exp(lambda)`.
@@ -66,33 +73,61 @@ pub fn exp1<R: Rng+?Sized>(rng: &mut R) -> f64 {
 /// let exp = Exp::new(2.0);
 /// let v = exp.sample(&mut rand::thread_rng());
 /// println!("{} is from a Exp(2) distribution", v);
+///
+/// let exp = Exp::new(2.0f32);
+/// let v: f32 = exp.sample(&mut rand::thread_rng());
+/// println!("{} is from a Exp(2) distribution", v);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Exp {
+pub struct Exp<F = f64> {
     /// `lambda` stored as `1/lambda`, since this is what we scale by.
-    lambda_inverse: f64
+    lambda_inverse: F
+}
+
+parameter_error! {
+    /// An error constructing an `Exp` distribution.
+    pub enum ExpError {
+        /// `lambda` is NaN or infinite.
+        LambdaNotFinite => "`lambda` is NaN or infinite",
+        /// `lambda <= 0`.
+        LambdaNotPositive => "`lambda` is not positive",
+    }
 }
 
-impl Exp {
+impl<F: Float> Exp<F> {
     /// Construct a new `Exp` with the given shape parameter
-    /// `lambda`. Panics if `lambda <= 0`.
+    /// `lambda`. Panics if `lambda` is not finite and positive; see
+    /// `try_new`.
     #[inline]
-    pub fn new(lambda: f64) -> Exp {
-        assert!(lambda > 0.0, "Exp::new called with `lambda` <= 0");
-        Exp { lambda_inverse: 1.0 / lambda }
+    pub fn new(lambda: F) -> Exp<F> {
+        Exp::try_new(lambda).unwrap_or_else(|e| panic!("Exp::new: {}", e))
+    }
+
+    /// Construct a new `Exp` with the given shape parameter `lambda`, or
+    /// return an error if `lambda` is NaN or infinite (`LambdaNotFinite`)
+    /// or `lambda <= 0` (`LambdaNotPositive`).
+    pub fn try_new(lambda: F) -> Result<Exp<F>, ExpError> {
+        if !lambda.is_finite() {
+            return Err(ExpError::LambdaNotFinite);
+        } else if lambda <= F::from_f64(0.0) {
+            return Err(ExpError::LambdaNotPositive);
+        }
+        Ok(Exp { lambda_inverse: F::from_f64(1.0) / lambda })
     }
 }
 
-impl Distribution<f64> for Exp {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
-        exp1(rng) * self.lambda_inverse
+impl<F: Float> Distribution<F> for Exp<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
+        exp1_float::<F, _>(rng) * self.lambda_inverse
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::f64;
     use distributions::{Distribution};
-    use super::Exp;
+    use distributions::test::assert_quantiles;
+    use super::{Exp, ExpError};
 
     #[test]
     fn test_exp() {
@@ -103,6 +138,11 @@ mod test {
         }
     }
     #[test]
+    fn test_exp_quantiles() {
+        assert_quantiles(Exp::new(2.0), |p| -(1. - p).ln() / 2.0);
+        assert_quantiles(Exp::new(2.0f32), |p| -(1. - p).ln() / 2.0);
+    }
+    #[test]
     #[should_panic]
     fn test_exp_invalid_lambda_zero() {
         Exp::new(0.0);
@@ -112,4 +152,11 @@ mod test {
     fn test_exp_invalid_lambda_neg() {
         Exp::new(-10.0);
     }
+    #[test]
+    fn test_exp_try_new() {
+        assert!(Exp::try_new(1e-300).is_ok());
+        assert_eq!(Exp::try_new(0.0).unwrap_err(), ExpError::LambdaNotPositive);
+        assert_eq!(Exp::try_new(f64::NAN).unwrap_err(), ExpError::LambdaNotFinite);
+        assert_eq!(Exp::try_new(f64::INFINITY).unwrap_err(), ExpError::LambdaNotFinite);
+    }
 }
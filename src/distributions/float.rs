@@ -0,0 +1,149 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The floating point types continuous distributions are generic over.
+
+use core::fmt::Debug;
+use core::ops::{Add, Sub, Mul, Div, Neg};
+
+use Rng;
+use distributions::{ziggurat_tables, Distribution, Uniform01, Open01};
+use utils::FloatConversions;
+
+/// A floating point type which distributions such as `Normal`, `Exp` and
+/// `Gamma` can sample.
+///
+/// This is implemented for `f32` and `f64`. `f32` distributions sample with
+/// `f32` arithmetic throughout, drawing their random bits with `next_u32`.
+///
+/// Besides the arithmetic needed by the distributions, this trait has some
+/// hidden items used for sampling; it is not meant to be implemented outside
+/// this crate.
+pub trait Float: 'static + Copy + Debug + PartialOrd
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Div<Output = Self> + Neg<Output = Self>
+{
+    /// Convert from an `f64`, rounding to the nearest value.
+    fn from_f64(x: f64) -> Self;
+
+    /// `e^self`.
+    fn exp(self) -> Self;
+    /// The natural logarithm.
+    fn ln(self) -> Self;
+    /// The square root.
+    fn sqrt(self) -> Self;
+    /// `self` raised to the power `n`.
+    fn powf(self, n: Self) -> Self;
+    /// The absolute value.
+    fn abs(self) -> Self;
+    /// Whether `self` is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+
+    #[doc(hidden)]
+    const ZIG_NORM_R: Self;
+    #[doc(hidden)]
+    fn zig_norm_x() -> ziggurat_tables::ZigTable<Self>;
+    #[doc(hidden)]
+    fn zig_norm_f() -> ziggurat_tables::ZigTable<Self>;
+    #[doc(hidden)]
+    const ZIG_EXP_R: Self;
+    #[doc(hidden)]
+    fn zig_exp_x() -> ziggurat_tables::ZigTable<Self>;
+    #[doc(hidden)]
+    fn zig_exp_f() -> ziggurat_tables::ZigTable<Self>;
+
+    /// Sample from `[0, 1)`.
+    #[doc(hidden)]
+    fn uniform01<R: Rng+?Sized>(rng: &mut R) -> Self;
+
+    /// Sample from `(0, 1)`.
+    #[doc(hidden)]
+    fn open01<R: Rng+?Sized>(rng: &mut R) -> Self;
+
+    /// Sample a value from `[-1, 1)` (if `symmetric`) or `[0, 1)` with a
+    /// fixed precision, together with a layer index in `0..256` taken from
+    /// the otherwise unused bits of the same random number.
+    #[doc(hidden)]
+    fn ziggurat_bits<R: Rng+?Sized>(rng: &mut R, symmetric: bool) -> (Self, usize);
+}
+
+macro_rules! float_impl {
+    ($ty:ident, $next_u:ident) => {
+        impl Float for $ty {
+            #[inline]
+            fn from_f64(x: f64) -> $ty { x as $ty }
+
+            #[inline]
+            fn exp(self) -> $ty { self.exp() }
+            #[inline]
+            fn ln(self) -> $ty { self.ln() }
+            #[inline]
+            fn sqrt(self) -> $ty { self.sqrt() }
+            #[inline]
+            fn powf(self, n: $ty) -> $ty { self.powf(n) }
+            #[inline]
+            fn abs(self) -> $ty { self.abs() }
+            #[inline]
+            fn is_finite(self) -> bool { self.is_finite() }
+
+            float_impl!(@tables $ty);
+
+            #[inline]
+            fn uniform01<R: Rng+?Sized>(rng: &mut R) -> $ty {
+                Uniform01.sample(rng)
+            }
+
+            #[inline]
+            fn open01<R: Rng+?Sized>(rng: &mut R) -> $ty {
+                Open01.sample(rng)
+            }
+
+            #[inline(always)]
+            fn ziggurat_bits<R: Rng+?Sized>(rng: &mut R, symmetric: bool) -> ($ty, usize) {
+                // The fixed-precision conversions only use the most
+                // significant bits, leaving the low byte for the index.
+                let bits = rng.$next_u();
+                let u = if symmetric {
+                    bits.closed_open11_fixed()
+                } else {
+                    bits.closed_open01_fixed()
+                };
+                (u, (bits & 0xff) as usize)
+            }
+        }
+    };
+    (@tables f64) => {
+        const ZIG_NORM_R: f64 = ziggurat_tables::ZIG_NORM_R;
+        #[inline]
+        fn zig_norm_x() -> ziggurat_tables::ZigTable<f64> { &ziggurat_tables::ZIG_NORM_X }
+        #[inline]
+        fn zig_norm_f() -> ziggurat_tables::ZigTable<f64> { &ziggurat_tables::ZIG_NORM_F }
+        const ZIG_EXP_R: f64 = ziggurat_tables::ZIG_EXP_R;
+        #[inline]
+        fn zig_exp_x() -> ziggurat_tables::ZigTable<f64> { &ziggurat_tables::ZIG_EXP_X }
+        #[inline]
+        fn zig_exp_f() -> ziggurat_tables::ZigTable<f64> { &ziggurat_tables::ZIG_EXP_F }
+    };
+    (@tables f32) => {
+        const ZIG_NORM_R: f32 = ziggurat_tables::ZIG_NORM_R_F32;
+        #[inline]
+        fn zig_norm_x() -> ziggurat_tables::ZigTable<f32> { &ziggurat_tables::ZIG_NORM_X_F32 }
+        #[inline]
+        fn zig_norm_f() -> ziggurat_tables::ZigTable<f32> { &ziggurat_tables::ZIG_NORM_F_F32 }
+        const ZIG_EXP_R: f32 = ziggurat_tables::ZIG_EXP_R_F32;
+        #[inline]
+        fn zig_exp_x() -> ziggurat_tables::ZigTable<f32> { &ziggurat_tables::ZIG_EXP_X_F32 }
+        #[inline]
+        fn zig_exp_f() -> ziggurat_tables::ZigTable<f32> { &ziggurat_tables::ZIG_EXP_F_F32 }
+    };
+}
+
+float_impl! { f32, next_u32 }
+float_impl! { f64, next_u64 }
@@ -16,8 +16,9 @@ use self::GammaRepr::*;
 use self::ChiSquaredRepr::*;
 
 use {Rng};
-use distributions::normal::standard_normal;
-use distributions::{Distribution, Exp, Rand, Open01};
+use distributions::normal::standard_normal_float;
+use distributions::{Distribution, Float};
+use distributions::exponential::exp1_float;
 
 /// The Gamma distribution `Gamma(shape, scale)` distribution.
 ///
@@ -43,6 +44,10 @@ use distributions::{Distribution, Exp, Rand, Open01};
 /// let gamma = Gamma::new(2.0, 5.0);
 /// let v = gamma.sample(&mut rand::thread_rng());
 /// println!("{} is from a Gamma(2, 5) distribution", v);
+///
+/// let gamma = Gamma::new(2.0f32, 5.0);
+/// let v: f32 = gamma.sample(&mut rand::thread_rng());
+/// println!("{} is from a Gamma(2, 5) distribution", v);
 /// ```
 ///
 /// [1]: George Marsaglia and Wai Wan Tsang. 2000. "A Simple Method
@@ -50,15 +55,16 @@ use distributions::{Distribution, Exp, Rand, Open01};
 /// (September 2000),
 /// 363-372. DOI:[10.1145/358407.358414](http://doi.acm.org/10.1145/358407.358414)
 #[derive(Clone, Copy, Debug)]
-pub struct Gamma {
-    repr: GammaRepr,
+pub struct Gamma<F = f64> {
+    repr: GammaRepr<F>,
 }
 
 #[derive(Clone, Copy, Debug)]
-enum GammaRepr {
-    Large(GammaLargeShape),
-    One(Exp),
-    Small(GammaSmallShape)
+enum GammaRepr<F> {
+    Large(GammaLargeShape<F>),
+    /// `shape == 1`: an exponential distribution with the given scale.
+    One(F),
+    Small(GammaSmallShape<F>)
 }
 
 // These two helpers could be made public, but saving the
@@ -76,9 +82,9 @@ enum GammaRepr {
 /// See `Gamma` for sampling from a Gamma distribution with general
 /// shape parameters.
 #[derive(Clone, Copy, Debug)]
-struct GammaSmallShape {
-    inv_shape: f64,
-    large_shape: GammaLargeShape
+struct GammaSmallShape<F> {
+    inv_shape: F,
+    large_shape: GammaLargeShape<F>
 }
 
 /// Gamma distribution where the shape parameter is larger than 1.
@@ -86,85 +92,120 @@ struct GammaSmallShape {
 /// See `Gamma` for sampling from a Gamma distribution with general
 /// shape parameters.
 #[derive(Clone, Copy, Debug)]
-struct GammaLargeShape {
-    scale: f64,
-    c: f64,
-    d: f64
+struct GammaLargeShape<F> {
+    scale: F,
+    c: F,
+    d: F
 }
 
-impl Gamma {
+parameter_error! {
+    /// An error constructing a `Gamma` distribution.
+    pub enum GammaError {
+        /// `shape` is NaN or infinite.
+        ShapeNotFinite => "`shape` is NaN or infinite",
+        /// `shape <= 0`.
+        ShapeNotPositive => "`shape` is not positive",
+        /// `scale` is NaN or infinite.
+        ScaleNotFinite => "`scale` is NaN or infinite",
+        /// `scale <= 0`.
+        ScaleNotPositive => "`scale` is not positive",
+    }
+}
+
+impl<F: Float> Gamma<F> {
     /// Construct an object representing the `Gamma(shape, scale)`
     /// distribution.
     ///
-    /// Panics if `shape <= 0` or `scale <= 0`.
+    /// Panics if `shape` or `scale` is not finite and positive; see
+    /// `try_new`.
     #[inline]
-    pub fn new(shape: f64, scale: f64) -> Gamma {
-        assert!(shape > 0.0, "Gamma::new called with shape <= 0");
-        assert!(scale > 0.0, "Gamma::new called with scale <= 0");
+    pub fn new(shape: F, scale: F) -> Gamma<F> {
+        Gamma::try_new(shape, scale).unwrap_or_else(|e| panic!("Gamma::new: {}", e))
+    }
+
+    /// Construct an object representing the `Gamma(shape, scale)`
+    /// distribution, or return an error if the parameters are invalid.
+    ///
+    /// Both `shape` and `scale` must be finite and positive. NaN or
+    /// infinite values give `ShapeNotFinite` or `ScaleNotFinite`; finite
+    /// values `<= 0` give `ShapeNotPositive` or `ScaleNotPositive`.
+    pub fn try_new(shape: F, scale: F) -> Result<Gamma<F>, GammaError> {
+        let (zero, one) = (F::from_f64(0.0), F::from_f64(1.0));
+        if !shape.is_finite() {
+            return Err(GammaError::ShapeNotFinite);
+        } else if shape <= zero {
+            return Err(GammaError::ShapeNotPositive);
+        } else if !scale.is_finite() {
+            return Err(GammaError::ScaleNotFinite);
+        } else if scale <= zero {
+            return Err(GammaError::ScaleNotPositive);
+        }
 
-        let repr = if shape == 1.0 {
-            One(Exp::new(1.0 / scale))
-        } else if shape < 1.0 {
+        let repr = if shape == one {
+            One(scale)
+        } else if shape < one {
             Small(GammaSmallShape::new_raw(shape, scale))
         } else {
             Large(GammaLargeShape::new_raw(shape, scale))
         };
-        Gamma { repr: repr }
+        Ok(Gamma { repr: repr })
     }
 }
 
-impl GammaSmallShape {
-    fn new_raw(shape: f64, scale: f64) -> GammaSmallShape {
+impl<F: Float> GammaSmallShape<F> {
+    fn new_raw(shape: F, scale: F) -> GammaSmallShape<F> {
+        let one = F::from_f64(1.0);
         GammaSmallShape {
-            inv_shape: 1. / shape,
-            large_shape: GammaLargeShape::new_raw(shape + 1.0, scale)
+            inv_shape: one / shape,
+            large_shape: GammaLargeShape::new_raw(shape + one, scale)
         }
     }
 }
 
-impl GammaLargeShape {
-    fn new_raw(shape: f64, scale: f64) -> GammaLargeShape {
-        let d = shape - 1. / 3.;
+impl<F: Float> GammaLargeShape<F> {
+    fn new_raw(shape: F, scale: F) -> GammaLargeShape<F> {
+        let d = shape - F::from_f64(1. / 3.);
         GammaLargeShape {
             scale: scale,
-            c: 1. / (9. * d).sqrt(),
+            c: F::from_f64(1.0) / (F::from_f64(9.) * d).sqrt(),
             d: d
         }
     }
 }
 
 
-impl Distribution<f64> for Gamma {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for Gamma<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
         match self.repr {
             Small(ref g) => g.sample(rng),
-            One(ref g) => g.sample(rng),
+            One(scale) => exp1_float::<F, _>(rng) * scale,
             Large(ref g) => g.sample(rng),
         }
     }
 }
-impl Distribution<f64> for GammaSmallShape {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
-        let u = f64::rand(rng, Open01);
+impl<F: Float> Distribution<F> for GammaSmallShape<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
+        let u = F::open01(rng);
 
         self.large_shape.sample(rng) * u.powf(self.inv_shape)
     }
 }
-impl Distribution<f64> for GammaLargeShape {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for GammaLargeShape<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
+        let one = F::from_f64(1.0);
         loop {
-            let x = standard_normal(rng);
-            let v_cbrt = 1.0 + self.c * x;
-            if v_cbrt <= 0.0 { // a^3 <= 0 iff a <= 0
+            let x: F = standard_normal_float(rng);
+            let v_cbrt = one + self.c * x;
+            if v_cbrt <= F::from_f64(0.0) { // a^3 <= 0 iff a <= 0
                 continue
             }
 
             let v = v_cbrt * v_cbrt * v_cbrt;
-            let u = f64::rand(rng, Open01);
+            let u = F::open01(rng);
 
             let x_sqr = x * x;
-            if u < 1.0 - 0.0331 * x_sqr * x_sqr ||
-                u.ln() < 0.5 * x_sqr + self.d * (1.0 - v + v.ln()) {
+            if u < one - F::from_f64(0.0331) * x_sqr * x_sqr ||
+                u.ln() < F::from_f64(0.5) * x_sqr + self.d * (one - v + v.ln()) {
                 return self.d * v * self.scale
             }
         }
@@ -189,38 +230,65 @@ impl Distribution<f64> for GammaLargeShape {
 /// println!("{} is from a χ²(11) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct ChiSquared {
-    repr: ChiSquaredRepr,
+pub struct ChiSquared<F = f64> {
+    repr: ChiSquaredRepr<F>,
 }
 
 #[derive(Clone, Copy, Debug)]
-enum ChiSquaredRepr {
+enum ChiSquaredRepr<F> {
     // k == 1, Gamma(alpha, ..) is particularly slow for alpha < 1,
     // e.g. when alpha = 1/2 as it would be for this case, so special-
     // casing and using the definition of N(0,1)^2 is faster.
     DoFExactlyOne,
-    DoFAnythingElse(Gamma),
+    DoFAnythingElse(Gamma<F>),
 }
 
-impl ChiSquared {
+parameter_error! {
+    /// An error constructing a `ChiSquared` distribution.
+    pub enum ChiSquaredError {
+        /// The degrees of freedom `k` is NaN or infinite.
+        DofNotFinite => "`k` is NaN or infinite",
+        /// The degrees of freedom `k <= 0`.
+        DofNotPositive => "`k` is not positive",
+    }
+}
+
+impl<F: Float> ChiSquared<F> {
     /// Create a new chi-squared distribution with degrees-of-freedom
-    /// `k`. Panics if `k < 0`.
-    pub fn new(k: f64) -> ChiSquared {
-        let repr = if k == 1.0 {
+    /// `k`. Panics if `k` is not finite and positive; see `try_new`.
+    pub fn new(k: F) -> ChiSquared<F> {
+        ChiSquared::try_new(k).unwrap_or_else(|e| panic!("ChiSquared::new: {}", e))
+    }
+
+    /// Create a new chi-squared distribution with degrees-of-freedom
+    /// `k`, or return an error if `k` is NaN or infinite (`DofNotFinite`)
+    /// or `k <= 0` (`DofNotPositive`). A `k` so small that `k / 2` rounds
+    /// to zero also gives `DofNotPositive`.
+    pub fn try_new(k: F) -> Result<ChiSquared<F>, ChiSquaredError> {
+        if !k.is_finite() {
+            return Err(ChiSquaredError::DofNotFinite);
+        } else if k <= F::from_f64(0.0) {
+            return Err(ChiSquaredError::DofNotPositive);
+        }
+        let repr = if k == F::from_f64(1.0) {
             DoFExactlyOne
         } else {
-            assert!(k > 0.0, "ChiSquared::new called with `k` < 0");
-            DoFAnythingElse(Gamma::new(0.5 * k, 2.0))
+            let gamma = Gamma::try_new(F::from_f64(0.5) * k, F::from_f64(2.0))
+                .map_err(|e| match e {
+                    GammaError::ShapeNotFinite => ChiSquaredError::DofNotFinite,
+                    _ => ChiSquaredError::DofNotPositive,
+                })?;
+            DoFAnythingElse(gamma)
         };
-        ChiSquared { repr: repr }
+        Ok(ChiSquared { repr: repr })
     }
 }
-impl Distribution<f64> for ChiSquared {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for ChiSquared<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
         match self.repr {
             DoFExactlyOne => {
                 // k == 1 => N(0,1)^2
-                let norm = standard_normal(rng);
+                let norm: F = standard_normal_float(rng);
                 norm * norm
             }
             DoFAnythingElse(ref g) => g.sample(rng)
@@ -244,30 +312,72 @@ impl Distribution<f64> for ChiSquared {
 /// println!("{} is from an F(2, 32) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct FisherF {
-    numer: ChiSquared,
-    denom: ChiSquared,
+pub struct FisherF<F = f64> {
+    numer: ChiSquared<F>,
+    denom: ChiSquared<F>,
     // denom_dof / numer_dof so that this can just be a straight
     // multiplication, rather than a division.
-    dof_ratio: f64,
+    dof_ratio: F,
 }
 
-impl FisherF {
+parameter_error! {
+    /// An error constructing a `FisherF` distribution.
+    pub enum FisherFError {
+        /// The numerator degrees of freedom `m` is NaN or infinite.
+        MNotFinite => "`m` is NaN or infinite",
+        /// The numerator degrees of freedom `m <= 0`.
+        MNotPositive => "`m` is not positive",
+        /// The denominator degrees of freedom `n` is NaN or infinite.
+        NNotFinite => "`n` is NaN or infinite",
+        /// The denominator degrees of freedom `n <= 0`.
+        NNotPositive => "`n` is not positive",
+    }
+}
+
+impl<F: Float> FisherF<F> {
     /// Create a new `FisherF` distribution, with the given
-    /// parameter. Panics if either `m` or `n` are not positive.
-    pub fn new(m: f64, n: f64) -> FisherF {
-        assert!(m > 0.0, "FisherF::new called with `m < 0`");
-        assert!(n > 0.0, "FisherF::new called with `n < 0`");
-
-        FisherF {
-            numer: ChiSquared::new(m),
-            denom: ChiSquared::new(n),
-            dof_ratio: n / m
+    /// parameter. Panics if either `m` or `n` is not finite and positive;
+    /// see `try_new`.
+    pub fn new(m: F, n: F) -> FisherF<F> {
+        FisherF::try_new(m, n).unwrap_or_else(|e| panic!("FisherF::new: {}", e))
+    }
+
+    /// Create a new `FisherF` distribution, or return an error if the
+    /// parameters are invalid.
+    ///
+    /// Both `m` and `n` must be finite and positive: NaN or infinite values
+    /// give `MNotFinite` or `NNotFinite`, and finite values `<= 0` give
+    /// `MNotPositive` or `NNotPositive`, as do values rejected by
+    /// `ChiSquared::try_new`.
+    pub fn try_new(m: F, n: F) -> Result<FisherF<F>, FisherFError> {
+        let zero = F::from_f64(0.0);
+        if !m.is_finite() {
+            return Err(FisherFError::MNotFinite);
+        } else if m <= zero {
+            return Err(FisherFError::MNotPositive);
+        } else if !n.is_finite() {
+            return Err(FisherFError::NNotFinite);
+        } else if n <= zero {
+            return Err(FisherFError::NNotPositive);
         }
+
+        let numer = ChiSquared::try_new(m).map_err(|e| match e {
+            ChiSquaredError::DofNotFinite => FisherFError::MNotFinite,
+            ChiSquaredError::DofNotPositive => FisherFError::MNotPositive,
+        })?;
+        let denom = ChiSquared::try_new(n).map_err(|e| match e {
+            ChiSquaredError::DofNotFinite => FisherFError::NNotFinite,
+            ChiSquaredError::DofNotPositive => FisherFError::NNotPositive,
+        })?;
+        Ok(FisherF {
+            numer,
+            denom,
+            dof_ratio: n / m
+        })
     }
 }
-impl Distribution<f64> for FisherF {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for FisherF<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
         self.numer.sample(rng) / self.denom.sample(rng) * self.dof_ratio
     }
 }
@@ -285,33 +395,210 @@ impl Distribution<f64> for FisherF {
 /// println!("{} is from a t(11) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct StudentT {
-    chi: ChiSquared,
-    dof: f64
+pub struct StudentT<F = f64> {
+    chi: ChiSquared<F>,
+    dof: F
 }
 
-impl StudentT {
+parameter_error! {
+    /// An error constructing a `StudentT` distribution.
+    pub enum StudentTError {
+        /// The degrees of freedom `n` is NaN or infinite.
+        DofNotFinite => "`n` is NaN or infinite",
+        /// The degrees of freedom `n <= 0`.
+        DofNotPositive => "`n` is not positive",
+    }
+}
+
+impl<F: Float> StudentT<F> {
     /// Create a new Student t distribution with `n` degrees of
-    /// freedom. Panics if `n <= 0`.
-    pub fn new(n: f64) -> StudentT {
-        assert!(n > 0.0, "StudentT::new called with `n <= 0`");
-        StudentT {
-            chi: ChiSquared::new(n),
-            dof: n
+    /// freedom. Panics if `n` is not finite and positive; see `try_new`.
+    pub fn new(n: F) -> StudentT<F> {
+        StudentT::try_new(n).unwrap_or_else(|e| panic!("StudentT::new: {}", e))
+    }
+
+    /// Create a new Student t distribution with `n` degrees of freedom, or
+    /// return an error if `n` is NaN or infinite (`DofNotFinite`) or
+    /// `n <= 0` (`DofNotPositive`), or `ChiSquared::try_new` rejects `n`.
+    pub fn try_new(n: F) -> Result<StudentT<F>, StudentTError> {
+        if !n.is_finite() {
+            return Err(StudentTError::DofNotFinite);
+        } else if n <= F::from_f64(0.0) {
+            return Err(StudentTError::DofNotPositive);
         }
+        let chi = ChiSquared::try_new(n).map_err(|e| match e {
+            ChiSquaredError::DofNotFinite => StudentTError::DofNotFinite,
+            ChiSquaredError::DofNotPositive => StudentTError::DofNotPositive,
+        })?;
+        Ok(StudentT {
+            chi,
+            dof: n
+        })
     }
 }
-impl Distribution<f64> for StudentT {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
-        let norm = standard_normal(rng);
+impl<F: Float> Distribution<F> for StudentT<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
+        let norm: F = standard_normal_float(rng);
         norm * (self.dof / self.chi.sample(rng)).sqrt()
     }
 }
 
+/// The Beta distribution `Beta(alpha, beta)`, with support `[0, 1]`.
+///
+/// This is sampled as `X / (X + Y)`, where `X` and `Y` are independent and
+/// follow the `Gamma(alpha, 1)` and `Gamma(beta, 1)` distributions
+/// respectively. If both `X` and `Y` underflow to zero, which happens
+/// almost always when both `alpha` and `beta` are tiny, the distribution
+/// is very close to taking the value 1 with probability
+/// `alpha / (alpha + beta)` and otherwise 0, so that is sampled instead.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Beta, Distribution};
+///
+/// let beta = Beta::new(2.0, 5.0);
+/// let v = beta.sample(&mut rand::thread_rng());
+/// println!("{} is from a Beta(2, 5) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Beta<F = f64> {
+    gamma_a: Gamma<F>,
+    gamma_b: Gamma<F>,
+    // alpha / (alpha + beta), computed without overflowing
+    mean: F,
+}
+
+parameter_error! {
+    /// An error constructing a `Beta` distribution.
+    pub enum BetaError {
+        /// `alpha` is NaN or infinite.
+        AlphaNotFinite => "`alpha` is NaN or infinite",
+        /// `alpha <= 0`.
+        AlphaNotPositive => "`alpha` is not positive",
+        /// `beta` is NaN or infinite.
+        BetaNotFinite => "`beta` is NaN or infinite",
+        /// `beta <= 0`.
+        BetaNotPositive => "`beta` is not positive",
+    }
+}
+
+impl<F: Float> Beta<F> {
+    /// Construct an object representing the `Beta(alpha, beta)`
+    /// distribution.
+    ///
+    /// Panics if `alpha` or `beta` is not finite and positive; see
+    /// `try_new`.
+    pub fn new(alpha: F, beta: F) -> Beta<F> {
+        Beta::try_new(alpha, beta).unwrap_or_else(|e| panic!("Beta::new: {}", e))
+    }
+
+    /// Construct an object representing the `Beta(alpha, beta)`
+    /// distribution, or return an error if the parameters are invalid.
+    ///
+    /// Both `alpha` and `beta` must be finite and positive: NaN or infinite
+    /// values give `AlphaNotFinite` or `BetaNotFinite`, and finite values
+    /// `<= 0` give `AlphaNotPositive` or `BetaNotPositive`.
+    pub fn try_new(alpha: F, beta: F) -> Result<Beta<F>, BetaError> {
+        let (zero, one) = (F::from_f64(0.0), F::from_f64(1.0));
+        if !alpha.is_finite() {
+            return Err(BetaError::AlphaNotFinite);
+        } else if alpha <= zero {
+            return Err(BetaError::AlphaNotPositive);
+        } else if !beta.is_finite() {
+            return Err(BetaError::BetaNotFinite);
+        } else if beta <= zero {
+            return Err(BetaError::BetaNotPositive);
+        }
+        Ok(Beta {
+            gamma_a: Gamma::new(alpha, one),
+            gamma_b: Gamma::new(beta, one),
+            mean: one / (one + beta / alpha),
+        })
+    }
+}
+
+impl<F: Float> Distribution<F> for Beta<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
+        let (zero, one) = (F::from_f64(0.0), F::from_f64(1.0));
+        let x = self.gamma_a.sample(rng);
+        let y = self.gamma_b.sample(rng);
+        if x + y > zero {
+            x / (x + y)
+        } else if F::open01(rng) < self.mean {
+            // Both samples underflowed, so fall back to the limit of
+            // `Beta(alpha, beta)` for small shapes. Retrying instead could
+            // take practically forever.
+            one
+        } else {
+            zero
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use std::f64;
     use distributions::{Distribution};
-    use super::{ChiSquared, StudentT, FisherF};
+    use super::{Gamma, ChiSquared, StudentT, FisherF, Beta};
+    use super::{GammaError, ChiSquaredError, StudentTError, FisherFError, BetaError};
+
+    #[test]
+    fn test_gamma_try_new() {
+        assert!(Gamma::try_new(0.5, 2.0).is_ok());
+        assert_eq!(Gamma::try_new(0.0, 1.0).unwrap_err(), GammaError::ShapeNotPositive);
+        assert_eq!(Gamma::try_new(f64::NAN, 1.0).unwrap_err(), GammaError::ShapeNotFinite);
+        assert_eq!(Gamma::try_new(f64::INFINITY, 1.0).unwrap_err(),
+                   GammaError::ShapeNotFinite);
+        assert_eq!(Gamma::try_new(1.0, -1.0).unwrap_err(), GammaError::ScaleNotPositive);
+        assert_eq!(Gamma::try_new(1.0, f64::NAN).unwrap_err(), GammaError::ScaleNotFinite);
+    }
+    #[test]
+    fn test_gamma_f32_moments() {
+        // one shape for each of the small, one and large representations
+        for &(shape, scale) in [(0.5f32, 2.0f32), (1.0, 3.0), (5.0, 0.5)].iter() {
+            let gamma = Gamma::new(shape, scale);
+            let mut rng = ::test::rng();
+            let n = 10_000;
+            let samples: Vec<f64> = (0..n).map(|_| gamma.sample(&mut rng) as f64).collect();
+            assert!(samples.iter().all(|&x| x > 0.0));
+            let mean = samples.iter().sum::<f64>() / n as f64;
+            let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+                / (n - 1) as f64;
+            let (shape, scale) = (shape as f64, scale as f64);
+            // The fourth central moment is 3k(k+2)θ^4, so the variance of the
+            // sample variance is about (3k(k+2) - k^2)θ^4 / n.
+            let mean_err = 6. * (shape / n as f64).sqrt() * scale;
+            let var_err = 6. * ((2. * shape * shape + 6. * shape) / n as f64).sqrt()
+                * scale * scale;
+            assert!((mean - shape * scale).abs() < mean_err,
+                    "mean {} for Gamma({}, {})", mean, shape, scale);
+            assert!((var - shape * scale * scale).abs() < var_err,
+                    "variance {} for Gamma({}, {})", var, shape, scale);
+        }
+    }
+    #[test]
+    fn test_f32_chi_squared_student_t_fisher_f_beta() {
+        let mut rng = ::test::rng();
+        let chi = ChiSquared::new(1.0f32);
+        let chi_large = ChiSquared::new(30.0f32);
+        let t = StudentT::new(11.0f32);
+        let f = FisherF::new(2.0f32, 32.0);
+        let beta = Beta::new(0.5f32, 2.0);
+        for _ in 0..1000 {
+            assert!(chi.sample(&mut rng) >= 0.0);
+            assert!(chi_large.sample(&mut rng) > 0.0);
+            assert!(t.sample(&mut rng).is_finite());
+            assert!(f.sample(&mut rng) >= 0.0);
+            let x = beta.sample(&mut rng);
+            assert!(x >= 0.0 && x <= 1.0);
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn test_gamma_invalid_nan() {
+        Gamma::new(f64::NAN, 1.0);
+    }
 
     #[test]
     fn test_chi_squared_one() {
@@ -343,6 +630,16 @@ mod test {
         ChiSquared::new(-1.0);
     }
 
+    #[test]
+    fn test_chi_squared_try_new() {
+        assert!(ChiSquared::try_new(1.0).is_ok());
+        assert_eq!(ChiSquared::try_new(0.0).unwrap_err(), ChiSquaredError::DofNotPositive);
+        assert_eq!(ChiSquared::try_new(f64::NAN).unwrap_err(), ChiSquaredError::DofNotFinite);
+        // `k / 2` underflows to zero
+        assert_eq!(ChiSquared::try_new(f64::from_bits(1)).unwrap_err(),
+                   ChiSquaredError::DofNotPositive);
+    }
+
     #[test]
     fn test_f() {
         let f = FisherF::new(2.0, 32.0);
@@ -352,6 +649,77 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_f_try_new() {
+        assert!(FisherF::try_new(2.0, 32.0).is_ok());
+        assert_eq!(FisherF::try_new(-2.0, 32.0).unwrap_err(), FisherFError::MNotPositive);
+        assert_eq!(FisherF::try_new(2.0, f64::INFINITY).unwrap_err(),
+                   FisherFError::NNotFinite);
+        assert_eq!(FisherF::try_new(f64::from_bits(1), 32.0).unwrap_err(),
+                   FisherFError::MNotPositive);
+        assert_eq!(FisherF::try_new(2.0, f64::from_bits(1)).unwrap_err(),
+                   FisherFError::NNotPositive);
+    }
+    #[test]
+    #[should_panic]
+    fn test_f_invalid_dof() {
+        FisherF::new(2.0, 0.0);
+    }
+
+    #[test]
+    fn test_beta_moments() {
+        let mut rng = ::test::rng();
+        let n = 10_000;
+        for &(a, b) in [(0.5, 0.5), (1.0, 1.0), (2.0, 5.0), (30.0, 3.0), (0.01, 2.0)].iter() {
+            let beta = Beta::new(a, b);
+            let samples: Vec<f64> = (0..n).map(|_| beta.sample(&mut rng)).collect();
+            assert!(samples.iter().all(|&x| x >= 0.0 && x <= 1.0));
+
+            let mean = samples.iter().sum::<f64>() / n as f64;
+            let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+                / (n - 1) as f64;
+            let expected_mean = a / (a + b);
+            let expected_var = a * b / ((a + b) * (a + b) * (a + b + 1.));
+            // the fourth central moment of a variable on [0, 1] is at most
+            // its variance, which bounds the standard error of the sample
+            // variance by sqrt(var / n)
+            assert!((mean - expected_mean).abs() < 6. * (expected_var / n as f64).sqrt(),
+                    "mean {} for Beta({}, {})", mean, a, b);
+            assert!((var - expected_var).abs() < 6. * (expected_var / n as f64).sqrt(),
+                    "variance {} for Beta({}, {})", var, a, b);
+        }
+    }
+    #[test]
+    fn test_beta_tiny_shapes() {
+        // Both gamma samples almost always underflow here, so this would
+        // not finish if sampling retried until one of them does not.
+        let mut rng = ::test::rng();
+        let n = 10_000;
+        let beta = Beta::new(1e-10, 3e-10);
+        let samples: Vec<f64> = (0..n).map(|_| beta.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= 0.0 && x <= 1.0));
+        // the mean is 0.25, with a standard deviation of about 0.43
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        assert!((mean - 0.25).abs() < 6. * 0.43 / (n as f64).sqrt(), "mean {}", mean);
+
+        let beta = Beta::<f32>::new(1e-6, 1e-6);
+        let samples: Vec<f32> = (0..n).map(|_| beta.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= 0.0 && x <= 1.0));
+        let mean = samples.iter().sum::<f32>() / n as f32;
+        assert!((mean - 0.5).abs() < 6. * 0.5 / (n as f32).sqrt(), "mean {}", mean);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_beta_invalid_alpha() {
+        Beta::new(0.0, 1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_beta_invalid_beta() {
+        Beta::new(1.0, -1.0);
+    }
+
     #[test]
     fn test_t() {
         let t = StudentT::new(11.0);
@@ -360,4 +728,18 @@ mod test {
             t.sample(&mut rng);
         }
     }
+    #[test]
+    fn test_t_try_new() {
+        assert!(StudentT::try_new(0.5).is_ok());
+        assert_eq!(StudentT::try_new(-1.0).unwrap_err(), StudentTError::DofNotPositive);
+        assert_eq!(StudentT::try_new(f64::NAN).unwrap_err(), StudentTError::DofNotFinite);
+        assert_eq!(StudentT::try_new(f64::from_bits(1)).unwrap_err(),
+                   StudentTError::DofNotPositive);
+    }
+
+    #[test]
+    fn test_beta_try_new() {
+        assert_eq!(Beta::try_new(f64::NAN, 1.0).unwrap_err(), BetaError::AlphaNotFinite);
+        assert_eq!(Beta::try_new(1.0, 0.0).unwrap_err(), BetaError::BetaNotPositive);
+    }
 }
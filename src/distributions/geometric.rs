@@ -0,0 +1,133 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The geometric distribution.
+
+use Rng;
+use distributions::{Distribution, Open01, Rand};
+
+/// The geometric distribution `Geometric(p)`: the number of failures before
+/// the first success in a series of Bernoulli trials, each succeeding with
+/// probability `p`.
+///
+/// This distribution has probability mass function:
+/// `f(k) = (1 - p)^k * p` for `k >= 0`.
+///
+/// Samples are generated by inversion of the CDF, so sampling takes constant
+/// time regardless of `p`. Values too large for a `u64`, which are only
+/// possible for tiny `p`, saturate to `u64::MAX`.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Geometric, Distribution};
+///
+/// let geo = Geometric::new(0.25);
+/// let v = geo.sample(&mut rand::thread_rng());
+/// println!("{} is from a Geometric(0.25) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Geometric {
+    /// `1 / ln(1 - p)`
+    inv_ln_q: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Geometric` distribution.
+    pub enum GeometricError {
+        /// `p` is NaN or outside `(0, 1]`.
+        ProbabilityOutOfRange => "`p` is not in (0, 1]",
+    }
+}
+
+impl Geometric {
+    /// Construct a new `Geometric` with the given probability of success
+    /// `p`. Panics if `p` is NaN, `p <= 0` or `p > 1`; see `try_new`.
+    pub fn new(p: f64) -> Geometric {
+        Geometric::try_new(p).unwrap_or_else(|e| panic!("Geometric::new: {}", e))
+    }
+
+    /// Construct a new `Geometric` with the given probability of success
+    /// `p`, or return `ProbabilityOutOfRange` if `p` is NaN or not in
+    /// `(0, 1]`.
+    pub fn try_new(p: f64) -> Result<Geometric, GeometricError> {
+        if !(p > 0.0 && p <= 1.0) {
+            return Err(GeometricError::ProbabilityOutOfRange);
+        }
+        // ln_1p keeps this accurate for tiny p; for p == 1 this is -0
+        Ok(Geometric { inv_ln_q: 1. / (-p).ln_1p() })
+    }
+}
+
+impl Distribution<u64> for Geometric {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        // P(floor(ln(u) / ln(q)) >= k) = P(u <= q^k) = q^k
+        let u = f64::rand(rng, Open01);
+        (u.ln() * self.inv_ln_q).floor() as u64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use SeedableRng;
+    use distributions::Distribution;
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{Geometric, GeometricError};
+
+    #[test]
+    fn test_geometric_fit() {
+        for &p in [0.9, 0.5, 0.1, 0.003].iter() {
+            let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+            let geo = Geometric::new(p);
+            let samples: Vec<u64> = (0..20_000).map(|_| geo.sample(&mut rng)).collect();
+            assert_discrete_fit(&samples, |k| (k as f64 * (-p).ln_1p()).exp() * p);
+        }
+    }
+
+    #[test]
+    fn test_geometric_tiny_p() {
+        let mut rng = ::test::rng();
+        let p = 1e-12;
+        let geo = Geometric::new(p);
+        let n = 10_000;
+        let mean = (0..n).map(|_| geo.sample(&mut rng) as f64).sum::<f64>() / n as f64;
+        // the mean and standard deviation are both about 1/p
+        assert!((mean * p - 1.).abs() < 6. / (n as f64).sqrt(), "{}", mean);
+    }
+
+    #[test]
+    fn test_geometric_one() {
+        let mut rng = ::test::rng();
+        let geo = Geometric::new(1.0);
+        for _ in 0..100 {
+            assert_eq!(geo.sample(&mut rng), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_geometric_invalid_p_zero() {
+        Geometric::new(0.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_geometric_invalid_p_large() {
+        Geometric::new(1.5);
+    }
+    #[test]
+    fn test_geometric_try_new() {
+        assert!(Geometric::try_new(1.0).is_ok());
+        for &p in [0.0, -0.5, 1.5, f64::NAN, f64::INFINITY].iter() {
+            assert_eq!(Geometric::try_new(p).unwrap_err(), GeometricError::ProbabilityOutOfRange);
+        }
+    }
+}
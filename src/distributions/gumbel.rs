@@ -0,0 +1,210 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Gumbel and Fréchet extreme value distributions.
+
+use Rng;
+use distributions::{Distribution, Open01, Rand};
+
+/// The Gumbel distribution `Gumbel(location, scale)`, also known as the type
+/// I extreme value distribution.
+///
+/// This distribution has CDF:
+/// `F(x) = exp(-exp(-(x - location) / scale))`.
+///
+/// Samples are generated by inversion of the CDF.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Gumbel, Distribution};
+///
+/// let gumbel = Gumbel::new(0.0, 1.0);
+/// let v = gumbel.sample(&mut rand::thread_rng());
+/// println!("{} is from a Gumbel(0, 1) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Gumbel {
+    location: f64,
+    scale: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Gumbel` distribution.
+    pub enum GumbelError {
+        /// `location` is NaN or infinite.
+        LocationNotFinite => "`location` is NaN or infinite",
+        /// `scale` is NaN or infinite.
+        ScaleNotFinite => "`scale` is NaN or infinite",
+        /// `scale <= 0`.
+        ScaleNotPositive => "`scale` is not positive",
+    }
+}
+
+impl Gumbel {
+    /// Construct a new `Gumbel` with the given `location` and `scale`
+    /// parameters. Panics if `location` is not finite or `scale` is not
+    /// finite and positive; see `try_new`.
+    pub fn new(location: f64, scale: f64) -> Gumbel {
+        Gumbel::try_new(location, scale).unwrap_or_else(|e| panic!("Gumbel::new: {}", e))
+    }
+
+    /// Construct a new `Gumbel`, or return an error if `location` is NaN or
+    /// infinite (`LocationNotFinite`), `scale` is NaN or infinite
+    /// (`ScaleNotFinite`) or `scale <= 0` (`ScaleNotPositive`).
+    pub fn try_new(location: f64, scale: f64) -> Result<Gumbel, GumbelError> {
+        if !location.is_finite() {
+            return Err(GumbelError::LocationNotFinite);
+        } else if !scale.is_finite() {
+            return Err(GumbelError::ScaleNotFinite);
+        } else if scale <= 0.0 {
+            return Err(GumbelError::ScaleNotPositive);
+        }
+        Ok(Gumbel { location, scale })
+    }
+}
+
+impl Distribution<f64> for Gumbel {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+        let x = f64::rand(rng, Open01);
+        self.location - self.scale * (-x.ln()).ln()
+    }
+}
+
+/// The Fréchet distribution `Frechet(location, scale, shape)`, also known as
+/// the type II extreme value distribution.
+///
+/// This distribution has CDF:
+/// `F(x) = exp(-((x - location) / scale)^(-shape))` for `x > location`.
+///
+/// Samples are generated by inversion of the CDF.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Frechet, Distribution};
+///
+/// let frechet = Frechet::new(0.0, 1.0, 2.0);
+/// let v = frechet.sample(&mut rand::thread_rng());
+/// println!("{} is from a Frechet(0, 1, 2) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Frechet {
+    location: f64,
+    scale: f64,
+    inv_neg_shape: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Frechet` distribution.
+    pub enum FrechetError {
+        /// `location` is NaN or infinite.
+        LocationNotFinite => "`location` is NaN or infinite",
+        /// `scale` is NaN or infinite.
+        ScaleNotFinite => "`scale` is NaN or infinite",
+        /// `scale <= 0`.
+        ScaleNotPositive => "`scale` is not positive",
+        /// `shape` is NaN or infinite.
+        ShapeNotFinite => "`shape` is NaN or infinite",
+        /// `shape <= 0`.
+        ShapeNotPositive => "`shape` is not positive",
+    }
+}
+
+impl Frechet {
+    /// Construct a new `Frechet` with the given `location`, `scale` and
+    /// `shape` parameters. Panics if `location` is not finite, or `scale`
+    /// or `shape` is not finite and positive; see `try_new`.
+    pub fn new(location: f64, scale: f64, shape: f64) -> Frechet {
+        Frechet::try_new(location, scale, shape)
+            .unwrap_or_else(|e| panic!("Frechet::new: {}", e))
+    }
+
+    /// Construct a new `Frechet`, or return an error if the parameters are
+    /// invalid. All three must be finite, giving `LocationNotFinite`,
+    /// `ScaleNotFinite` or `ShapeNotFinite` for NaN or infinite values, and
+    /// `scale` and `shape` must be positive (`ScaleNotPositive`,
+    /// `ShapeNotPositive`).
+    pub fn try_new(location: f64, scale: f64, shape: f64) -> Result<Frechet, FrechetError> {
+        if !location.is_finite() {
+            return Err(FrechetError::LocationNotFinite);
+        } else if !scale.is_finite() {
+            return Err(FrechetError::ScaleNotFinite);
+        } else if scale <= 0.0 {
+            return Err(FrechetError::ScaleNotPositive);
+        } else if !shape.is_finite() {
+            return Err(FrechetError::ShapeNotFinite);
+        } else if shape <= 0.0 {
+            return Err(FrechetError::ShapeNotPositive);
+        }
+        Ok(Frechet { location, scale, inv_neg_shape: -1. / shape })
+    }
+}
+
+impl Distribution<f64> for Frechet {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+        let x = f64::rand(rng, Open01);
+        self.location + self.scale * (-x.ln()).powf(self.inv_neg_shape)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use distributions::test::assert_quantiles;
+    use super::{Gumbel, Frechet, GumbelError, FrechetError};
+
+    #[test]
+    fn test_gumbel_quantiles() {
+        for &(location, scale) in [(0.0, 1.0), (-5.0, 0.1), (100.0, 30.0)].iter() {
+            assert_quantiles(Gumbel::new(location, scale),
+                             |p| location - scale * (-p.ln()).ln());
+        }
+    }
+
+    #[test]
+    fn test_frechet_quantiles() {
+        for &(location, scale, shape) in [(0.0, 1.0, 1.0), (-5.0, 0.1, 3.0),
+                                          (100.0, 30.0, 0.5)].iter() {
+            assert_quantiles(Frechet::new(location, scale, shape),
+                             |p| location + scale * (-p.ln()).powf(-1. / shape));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gumbel_invalid_scale() {
+        Gumbel::new(0.0, 0.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_frechet_invalid_scale() {
+        Frechet::new(0.0, -1.0, 1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_frechet_invalid_shape() {
+        Frechet::new(0.0, 1.0, 0.0);
+    }
+    #[test]
+    fn test_gumbel_try_new() {
+        assert_eq!(Gumbel::try_new(f64::NAN, 1.0).unwrap_err(), GumbelError::LocationNotFinite);
+        assert_eq!(Gumbel::try_new(0.0, -1.0).unwrap_err(), GumbelError::ScaleNotPositive);
+    }
+    #[test]
+    fn test_frechet_try_new() {
+        assert_eq!(Frechet::try_new(f64::INFINITY, 1.0, 1.0).unwrap_err(),
+                   FrechetError::LocationNotFinite);
+        assert_eq!(Frechet::try_new(0.0, f64::NAN, 1.0).unwrap_err(),
+                   FrechetError::ScaleNotFinite);
+        assert_eq!(Frechet::try_new(0.0, 1.0, -2.0).unwrap_err(),
+                   FrechetError::ShapeNotPositive);
+    }
+}
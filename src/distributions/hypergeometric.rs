@@ -0,0 +1,337 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The hypergeometric distribution.
+
+use Rng;
+use distributions::{log_gamma, Distribution, Uniform01, Open01, Rand};
+
+/// The hypergeometric distribution `Hypergeometric(N, K, n)`: the number of
+/// successes in `n` draws without replacement from a population of size `N`
+/// containing `K` successes.
+///
+/// This distribution has probability mass function:
+/// `f(k) = C(K, k) C(N - K, n - k) / C(N, n)`,
+/// where `C(a, b)` is the binomial coefficient.
+///
+/// Samples are generated with the algorithms of Kachitvichyanukul and
+/// Schmeiser[1]: inversion (HIN) when the mode is close to the lower end of
+/// the support, and otherwise rejection from a majorizing function made of a
+/// rectangle and two exponential tails (H2PE). Both are exact.
+///
+/// [1]: Voratas Kachitvichyanukul and Bruce W. Schmeiser (1985).
+/// *Computer generation of hypergeometric random variates*. Journal of
+/// Statistical Computation and Simulation 22, 2, 127-145.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Hypergeometric, Distribution};
+///
+/// // number of aces in a hand of five cards
+/// let hyper = Hypergeometric::new(52, 4, 5);
+/// let v = hyper.sample(&mut rand::thread_rng());
+/// println!("{} aces", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Hypergeometric {
+    /// The smaller of the number of successes and failures in the population.
+    n1: u64,
+    /// The larger of the number of successes and failures in the population.
+    n2: u64,
+    /// The number of draws, or undrawn items if that is smaller.
+    k: u64,
+    /// A sample `x` with the reduced parameters above is transformed to the
+    /// original problem as `offset_x + sign_x * x`.
+    offset_x: i64,
+    sign_x: i64,
+    method: Method,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Method {
+    Inversion {
+        /// `f(0)`
+        initial_p: f64,
+    },
+    Rejection {
+        m: f64,
+        /// `ln(f(m))` without the normalising constant
+        a: f64,
+        lambda_l: f64,
+        lambda_r: f64,
+        x_l: f64,
+        x_r: f64,
+        p1: f64,
+        p2: f64,
+        p3: f64,
+    },
+}
+
+/// `ln(x!)`, extended to real `x` via the gamma function.
+fn ln_factorial(x: f64) -> f64 {
+    log_gamma(x + 1.)
+}
+
+parameter_error! {
+    /// An error constructing a `Hypergeometric` distribution.
+    pub enum HypergeometricError {
+        /// `successes > total`.
+        TooManySuccesses => "`successes` is greater than `total`",
+        /// `n > total`.
+        TooManyDraws => "`n` is greater than `total`",
+    }
+}
+
+impl Hypergeometric {
+    /// Construct a new `Hypergeometric` for `n` draws from a population of
+    /// size `total` containing `successes` successes.
+    ///
+    /// Panics if `successes > total` or `n > total`; see `try_new`.
+    pub fn new(total: u64, successes: u64, n: u64) -> Hypergeometric {
+        Hypergeometric::try_new(total, successes, n)
+            .unwrap_or_else(|e| panic!("Hypergeometric::new: {}", e))
+    }
+
+    /// Construct a new `Hypergeometric`, or return an error if
+    /// `successes > total` (`TooManySuccesses`) or `n > total`
+    /// (`TooManyDraws`).
+    pub fn try_new(total: u64, successes: u64, n: u64)
+        -> Result<Hypergeometric, HypergeometricError>
+    {
+        if successes > total {
+            return Err(HypergeometricError::TooManySuccesses);
+        } else if n > total {
+            return Err(HypergeometricError::TooManyDraws);
+        }
+
+        // Reduce to n1 <= n2 by swapping successes and failures, and to
+        // k <= total / 2 by counting the undrawn items instead of the drawn
+        // ones. Both transformations are undone at the end of sampling.
+        let failures = total - successes;
+        let (n1, n2, mut sign_x, mut offset_x) = if successes > failures {
+            (failures, successes, -1, n as i64)
+        } else {
+            (successes, failures, 1, 0)
+        };
+        let k = if n <= total / 2 {
+            n
+        } else {
+            offset_x += sign_x * n1 as i64;
+            sign_x = -sign_x;
+            total - n
+        };
+        // Now k <= total / 2 <= n2, so the support is 0..=min(n1, k).
+
+        let (n1_f, n2_f, k_f, total_f) = (n1 as f64, n2 as f64, k as f64, total as f64);
+        let m = ((k_f + 1.) * (n1_f + 1.) / (total_f + 2.)).floor();
+
+        // H2PE is only efficient if the mode is far enough from zero.
+        const HIN_THRESHOLD: f64 = 10.;
+        let method = if m < HIN_THRESHOLD {
+            // f(0) = C(n2, k) / C(total, k) = prod_{i < n1} (1 - k / (total - i))
+            let ln_p: f64 = (0..n1.min(k)).map(|i| {
+                if n1 <= k {
+                    (-k_f / (total - i) as f64).ln_1p()
+                } else {
+                    (-n1_f / (total - i) as f64).ln_1p()
+                }
+            }).sum();
+            Method::Inversion { initial_p: ln_p.exp() }
+        } else {
+            let a = ln_factorial(m) + ln_factorial(n1_f - m) +
+                ln_factorial(k_f - m) + ln_factorial(n2_f - k_f + m);
+
+            let var = (total_f - k_f) * k_f * n1_f * n2_f /
+                ((total_f - 1.) * total_f * total_f);
+            let d = (1.5 * var.sqrt()).floor() + 0.5;
+            let x_l = m - d + 0.5;
+            let x_r = m + d + 0.5;
+
+            // f(x_l) / f(m) and f(x_r - 1) / f(m)
+            let k_l = (a - ln_factorial(x_l) - ln_factorial(n1_f - x_l) -
+                ln_factorial(k_f - x_l) - ln_factorial(n2_f - k_f + x_l)).exp();
+            let k_r = (a - ln_factorial(x_r - 1.) - ln_factorial(n1_f - x_r + 1.) -
+                ln_factorial(k_f - x_r + 1.) - ln_factorial(n2_f - k_f + x_r - 1.)).exp();
+
+            let lambda_l = -(x_l * (n2_f - k_f + x_l) /
+                ((n1_f - x_l + 1.) * (k_f - x_l + 1.))).ln();
+            let lambda_r = -((n1_f - x_r + 1.) * (k_f - x_r + 1.) /
+                (x_r * (n2_f - k_f + x_r))).ln();
+
+            let p1 = 2. * d;
+            let p2 = p1 + k_l / lambda_l;
+            let p3 = p2 + k_r / lambda_r;
+
+            Method::Rejection { m, a, lambda_l, lambda_r, x_l, x_r, p1, p2, p3 }
+        };
+
+        Ok(Hypergeometric { n1, n2, k, offset_x, sign_x, method })
+    }
+}
+
+impl Distribution<u64> for Hypergeometric {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        let (n1, n2, k) = (self.n1, self.n2, self.k);
+        let max_x = n1.min(k);
+
+        let x = match self.method {
+            Method::Inversion { initial_p } => {
+                // walk up the PMF from zero using
+                // f(x + 1) / f(x) = (n1 - x)(k - x) / ((x + 1)(n2 - k + x + 1))
+                let mut p = initial_p;
+                let mut u = f64::rand(rng, Uniform01);
+                let mut x = 0;
+                while u > p && x < max_x {
+                    u -= p;
+                    p *= ((n1 - x) as f64 * (k - x) as f64) /
+                        ((x + 1) as f64 * (n2 - k + x + 1) as f64);
+                    x += 1;
+                }
+                x
+            }
+            Method::Rejection { m, a, lambda_l, lambda_r, x_l, x_r, p1, p2, p3 } => {
+                let (n1_f, n2_f, k_f) = (n1 as f64, n2 as f64, k as f64);
+                loop {
+                    let u = p3 * f64::rand(rng, Uniform01);
+                    let mut v = f64::rand(rng, Open01);
+                    let y;
+                    if u <= p1 {
+                        // rectangle around the mode
+                        y = (x_l + u).floor();
+                    } else if u <= p2 {
+                        // left exponential tail
+                        y = (x_l + v.ln() / lambda_l).floor();
+                        if y < 0. {
+                            continue;
+                        }
+                        v *= (u - p1) * lambda_l;
+                    } else {
+                        // right exponential tail
+                        y = (x_r - v.ln() / lambda_r).floor();
+                        if y > max_x as f64 {
+                            continue;
+                        }
+                        v *= (u - p2) * lambda_r;
+                    }
+
+                    if m < 100. || y <= 50. {
+                        // evaluate f(y) / f(m) recursively
+                        let (yi, mi) = (y as u64, m as u64);
+                        let mut f = 1.;
+                        if mi < yi {
+                            for i in (mi + 1)..(yi + 1) {
+                                f *= (n1 - i + 1) as f64 * (k - i + 1) as f64;
+                                f /= i as f64 * (n2 - k + i) as f64;
+                            }
+                        } else {
+                            for i in (yi + 1)..(mi + 1) {
+                                f *= i as f64 * (n2 - k + i) as f64;
+                                f /= (n1 - i + 1) as f64 * (k - i + 1) as f64;
+                            }
+                        }
+                        if v <= f {
+                            break y as u64;
+                        }
+                    } else {
+                        // compare against ln(f(y) / f(m)) directly; the
+                        // squeeze of the paper is not needed since
+                        // `log_gamma` is accurate and cheap enough
+                        let ln_f = a - ln_factorial(y) - ln_factorial(n1_f - y) -
+                            ln_factorial(k_f - y) - ln_factorial(n2_f - k_f + y);
+                        if v.ln() <= ln_f {
+                            break y as u64;
+                        }
+                    }
+                }
+            }
+        };
+
+        (self.offset_x + self.sign_x * x as i64) as u64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use SeedableRng;
+    use distributions::{log_gamma, Distribution};
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{Hypergeometric, HypergeometricError};
+
+    fn ln_choose(n: u64, k: u64) -> f64 {
+        log_gamma(n as f64 + 1.) - log_gamma(k as f64 + 1.) - log_gamma((n - k) as f64 + 1.)
+    }
+
+    fn test_fit(total: u64, successes: u64, n: u64) {
+        let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+        let hyper = Hypergeometric::new(total, successes, n);
+        let samples: Vec<u64> = (0..20_000).map(|_| hyper.sample(&mut rng)).collect();
+        let min = (n + successes).saturating_sub(total);
+        let max = n.min(successes);
+        assert!(samples.iter().all(|&x| min <= x && x <= max));
+        assert_discrete_fit(&samples, |x| {
+            if x < min || x > max {
+                return 0.;
+            }
+            (ln_choose(successes, x) + ln_choose(total - successes, n - x)
+                - ln_choose(total, n)).exp()
+        });
+    }
+
+    #[test]
+    fn test_hypergeometric_inversion() {
+        test_fit(52, 4, 5);
+        test_fit(20, 5, 10);
+        test_fit(100, 90, 10);
+        test_fit(100, 5, 95);
+        test_fit(10_000, 20, 1_000);
+    }
+
+    #[test]
+    fn test_hypergeometric_rejection() {
+        // the mode is below 100: PMF ratios are evaluated recursively
+        test_fit(500, 100, 200);
+        test_fit(500, 400, 200);
+        test_fit(500, 100, 450);
+        // the mode is at least 100: PMF ratios use log_gamma
+        test_fit(1000, 400, 300);
+        test_fit(100_000, 50_000, 5_000);
+    }
+
+    #[test]
+    fn test_hypergeometric_degenerate() {
+        let mut rng = ::test::rng();
+        assert_eq!(Hypergeometric::new(10, 0, 5).sample(&mut rng), 0);
+        assert_eq!(Hypergeometric::new(10, 10, 5).sample(&mut rng), 5);
+        assert_eq!(Hypergeometric::new(10, 3, 10).sample(&mut rng), 3);
+        assert_eq!(Hypergeometric::new(10, 3, 0).sample(&mut rng), 0);
+        assert_eq!(Hypergeometric::new(0, 0, 0).sample(&mut rng), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hypergeometric_invalid_successes() {
+        Hypergeometric::new(10, 11, 5);
+    }
+    #[test]
+    #[should_panic]
+    fn test_hypergeometric_invalid_n() {
+        Hypergeometric::new(10, 5, 11);
+    }
+    #[test]
+    fn test_hypergeometric_try_new() {
+        assert!(Hypergeometric::try_new(0, 0, 0).is_ok());
+        assert_eq!(Hypergeometric::try_new(10, 11, 5).unwrap_err(),
+                   HypergeometricError::TooManySuccesses);
+        assert_eq!(Hypergeometric::try_new(10, 5, 11).unwrap_err(),
+                   HypergeometricError::TooManyDraws);
+    }
+}
@@ -17,35 +17,134 @@
 //!
 //! TODO: is it worth exposing both submodules and re-exporting their members?
 
+use core::marker::PhantomData;
+
 use Rng;
 
 pub use self::default::Default;
 pub use self::uniform::{uniform, codepoint, ascii_word_char};
 pub use self::uniform::{Uniform, Uniform01, Open01, Closed01, AsciiWordChar};
-pub use self::range::Range;
+pub use self::range::{Range, RangeError};
+pub use self::charset::{Alphanumeric, HexDigit, Base64Char};
+#[cfg(feature="std")]
+pub use self::charset::{Charset, CharsetError};
 
 #[cfg(feature="std")]
-pub use self::gamma::{Gamma, ChiSquared, FisherF, StudentT};
+pub use self::float::Float;
+#[cfg(feature="std")]
+pub use self::gamma::{Gamma, ChiSquared, FisherF, StudentT, Beta};
+#[cfg(feature="std")]
+pub use self::gamma::{GammaError, ChiSquaredError, FisherFError, StudentTError, BetaError};
+#[cfg(feature="std")]
+pub use self::dirichlet::{Dirichlet, DirichletError};
+#[cfg(feature="std")]
+pub use self::normal::{Normal, LogNormal, NormalError};
+#[cfg(feature="std")]
+pub use self::multivariate_normal::{MultivariateNormal, MultivariateNormalError};
+#[cfg(feature="std")]
+pub use self::exponential::{Exp, ExpError};
+#[cfg(feature="std")]
+pub use self::cauchy::{Cauchy, CauchyError};
+#[cfg(feature="std")]
+pub use self::weibull::{Weibull, WeibullError};
 #[cfg(feature="std")]
-pub use self::normal::{Normal, LogNormal};
+pub use self::pareto::{Pareto, ParetoError};
 #[cfg(feature="std")]
-pub use self::exponential::Exp;
+pub use self::gumbel::{Gumbel, Frechet, GumbelError, FrechetError};
+#[cfg(feature="std")]
+pub use self::triangular::{Triangular, TriangularError};
+#[cfg(feature="std")]
+pub use self::binomial::{Binomial, BinomialError};
+#[cfg(feature="std")]
+pub use self::poisson::{Poisson, PoissonError};
+#[cfg(feature="std")]
+pub use self::geometric::{Geometric, GeometricError};
+#[cfg(feature="std")]
+pub use self::hypergeometric::{Hypergeometric, HypergeometricError};
+#[cfg(feature="std")]
+pub use self::negative_binomial::{NegativeBinomial, NegativeBinomialError};
+#[cfg(feature="std")]
+pub use self::zipf::{Zipf, Zeta, ZipfError, ZetaError};
+#[cfg(feature="std")]
+pub use self::sphere::{UnitSphereSurface, UnitCircle, UnitBall, UnitDisc};
 
 use Sample;
 
+/// Define an error enum describing invalid distribution parameters, with a
+/// message for each variant used by both `Display` and `Error`.
+macro_rules! parameter_error {
+    ($(#[$attr:meta])* pub enum $name:ident {
+        $($(#[$vattr:meta])* $variant:ident => $msg:expr,)+
+    }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name {
+            $($(#[$vattr])* $variant,)+
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                f.write_str(match *self {
+                    $($name::$variant => $msg,)+
+                })
+            }
+        }
+
+        #[cfg(feature="std")]
+        impl ::std::error::Error for $name {
+            fn description(&self) -> &str {
+                match *self {
+                    $($name::$variant => $msg,)+
+                }
+            }
+        }
+    }
+}
+
 mod default;
 mod uniform;
+mod charset;
 #[cfg(feature="std")]
 mod ziggurat_tables;
+#[cfg(feature="std")]
+mod float;
 
 pub mod range;
 
 #[cfg(feature="std")]
 pub mod gamma;
 #[cfg(feature="std")]
+pub mod dirichlet;
+#[cfg(feature="std")]
 pub mod normal;
 #[cfg(feature="std")]
+pub mod multivariate_normal;
+#[cfg(feature="std")]
 pub mod exponential;
+#[cfg(feature="std")]
+pub mod cauchy;
+#[cfg(feature="std")]
+pub mod weibull;
+#[cfg(feature="std")]
+pub mod pareto;
+#[cfg(feature="std")]
+pub mod gumbel;
+#[cfg(feature="std")]
+pub mod triangular;
+#[cfg(feature="std")]
+pub mod binomial;
+#[cfg(feature="std")]
+pub mod poisson;
+#[cfg(feature="std")]
+pub mod geometric;
+#[cfg(feature="std")]
+pub mod hypergeometric;
+#[cfg(feature="std")]
+pub mod negative_binomial;
+#[cfg(feature="std")]
+pub mod zipf;
+#[cfg(feature="std")]
+pub mod sphere;
 
 
 /// Return a bool with a 1 in n chance of being true
@@ -63,7 +162,7 @@ pub mod exponential;
 /// println!("{}", weighted_bool(3, &mut rng));
 /// ```
 pub fn weighted_bool<R: Rng+?Sized>(n: u32, rng: &mut R) -> bool {
-    n <= 1 || rng.gen_range(0, n) == 0
+    n <= 1 || rng.gen_range(0..n) == 0
 }
 
 /// Types (distributions) that can be used to create a random instance of `T`.
@@ -71,12 +170,65 @@ pub trait Distribution<T> {
     /// Generate a random value of `T`, using `rng` as the
     /// source of randomness.
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> T;
+
+    /// Fill `dest` entirely with random values of `T`, using `rng` as the
+    /// source of randomness.
+    ///
+    /// The default implementation calls `sample` for each element. Some
+    /// distributions provide a faster implementation; e.g. `Uniform` fills
+    /// integer slices with a single call to `Rng::fill_bytes`. Such an
+    /// implementation may consume the output of `rng` differently from
+    /// repeated calls to `sample`, hence yield different values.
+    fn fill<R: Rng+?Sized>(&self, rng: &mut R, dest: &mut [T]) {
+        for x in dest.iter_mut() {
+            *x = self.sample(rng);
+        }
+    }
 }
 
 impl<'a, T, D: Distribution<T>> Distribution<T> for &'a D {
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> T {
         (*self).sample(rng)
     }
+
+    fn fill<R: Rng+?Sized>(&self, rng: &mut R, dest: &mut [T]) {
+        (*self).fill(rng, dest)
+    }
+}
+
+/// An iterator that generates random values of `T` with distribution `D`,
+/// using `R` as the source of randomness.
+///
+/// This `struct` is created by [`Sample::sample_iter`]. The iterator is
+/// infinite; use `take` or similar to limit its length.
+///
+/// [`Sample::sample_iter`]: ../trait.Sample.html#method.sample_iter
+#[derive(Debug)]
+pub struct DistIter<'a, D, R: ?Sized+'a, T> {
+    distr: D,
+    rng: &'a mut R,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, D, R: ?Sized+'a, T> DistIter<'a, D, R, T> {
+    pub(crate) fn new(distr: D, rng: &'a mut R) -> Self {
+        DistIter { distr, rng, phantom: PhantomData }
+    }
+}
+
+impl<'a, D, R, T> Iterator for DistIter<'a, D, R, T>
+    where D: Distribution<T>, R: Rng+?Sized+'a
+{
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<T> {
+        Some(self.distr.sample(self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (::core::usize::MAX, None)
+    }
 }
 
 /// Generic trait for sampling random values from some distribution
@@ -149,34 +301,26 @@ impl<T> SimpleRand for T where Default: Distribution<T> {
 // size from force-inlining.
 #[cfg(feature="std")]
 #[inline(always)]
-fn ziggurat<R: Rng+?Sized, P, Z>(
+fn ziggurat<F: Float, R: Rng+?Sized, P, Z>(
             rng: &mut R,
             symmetric: bool,
-            x_tab: ziggurat_tables::ZigTable,
-            f_tab: ziggurat_tables::ZigTable,
+            x_tab: ziggurat_tables::ZigTable<F>,
+            f_tab: ziggurat_tables::ZigTable<F>,
             mut pdf: P,
             mut zero_case: Z)
-            -> f64 where P: FnMut(f64) -> f64, Z: FnMut(&mut R, f64) -> f64 {
-    use utils::FloatConversions;
-    
+            -> F where P: FnMut(F) -> F, Z: FnMut(&mut R, F) -> F {
     loop {
-        // As an optimisation convert the random u64 to a f64 using only
-        // 53 bits, as many as will fit in the float's fraction.
-        // Of the remaining 11 least significant bits we use 8 to construct `i`.
+        // As an optimisation convert the random u64 (u32 for f32) to a float
+        // using only as many bits as fit in the float's fraction. Of the
+        // remaining least significant bits we use 8 to construct `i`.
         // This saves us generating a whole extra random number, while the added
-        // precision of using 64 bits for f64 does not buy us much.
-        let bits: u64 = uniform(rng);
-
+        // precision of using all the bits does not buy us much.
+        //
         // u is either U(-1, 1) or U(0, 1) depending on if this is a
         // symmetric distribution or not.
         // FIXME: the distribution is not open, but closed-open.
         //        Can that cause problems or a bias?
-        let u = if symmetric {
-                    bits.closed_open11_fixed()
-                } else {
-                    bits.closed_open01_fixed()
-                };
-        let i = (bits & 0xff) as usize;
+        let (u, i) = F::ziggurat_bits(rng, symmetric);
 
         let x = u * x_tab[i];
 
@@ -190,16 +334,144 @@ fn ziggurat<R: Rng+?Sized, P, Z>(
             return zero_case(rng, u);
         }
         // algebraically equivalent to f1 + DRanU()*(f0 - f1) < 1
-        if f_tab[i + 1] + (f_tab[i] - f_tab[i + 1]) * f64::rand(rng, Uniform01) < pdf(x) {
+        if f_tab[i + 1] + (f_tab[i] - f_tab[i + 1]) * F::uniform01(rng) < pdf(x) {
             return x;
         }
     }
 }
 
+/// Calculates `ln(gamma(x))` for `x >= 0.5`, using the Lanczos
+/// approximation with `g = 7` and `n = 9`, which is accurate to about 15
+/// significant digits.
+#[cfg(feature="std")]
+fn log_gamma(x: f64) -> f64 {
+    const G: f64 = 7.;
+    const COEFFICIENTS: [f64; 8] = [
+        676.5203681218851,
+        -1259.1392167224028,
+        771.3234287776531,
+        -176.6150291621406,
+        12.507343278686905,
+        -0.13857109526572012,
+        9.984369578019572e-6,
+        1.5056327351493116e-7,
+    ];
+    const LN_SQRT_2PI: f64 = 0.9189385332046728;
+
+    let x = x - 1.;
+    let mut a = 0.9999999999998099;
+    for (i, &c) in COEFFICIENTS.iter().enumerate() {
+        a += c / (x + (i + 1) as f64);
+    }
+    let t = x + G + 0.5;
+    LN_SQRT_2PI + (x + 0.5) * t.ln() - t + a.ln()
+}
+
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use {Rng, thread_rng};
     use distributions::{weighted_bool};
+    #[cfg(feature="std")]
+    use distributions::Distribution;
+
+    /// Assert that `samples` of a discrete distribution on the non-negative
+    /// integers fit its probability mass function `pmf`, with Pearson's
+    /// chi-squared test at significance level 0.001.
+    ///
+    /// Values are grouped so that each group has an expected count of at
+    /// least 5. The probability of values below the smallest sample is added
+    /// to the first group, and the tail where fewer than 10 samples are
+    /// expected forms the last group, so heavy tails are handled cheaply.
+    #[cfg(feature="std")]
+    pub fn assert_discrete_fit<F: Fn(u64) -> f64>(samples: &[u64], pmf: F) {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len() as f64;
+        let min = sorted[0];
+        let max = sorted[sorted.len() - 1];
+
+        // (observed, expected) counts of each group
+        let mut groups: Vec<(f64, f64)> = Vec::new();
+        let below: f64 = (0..min).map(&pmf).sum();
+        let (mut o, mut e) = (0., n * below);
+        let mut cum = below;
+        let mut i = 0;
+        let mut k = min;
+        while k <= max && n * (1. - cum) >= 10. {
+            let start = i;
+            while i < sorted.len() && sorted[i] == k {
+                i += 1;
+            }
+            let p = pmf(k);
+            cum += p;
+            o += (i - start) as f64;
+            e += n * p;
+            if e >= 5. {
+                groups.push((o, e));
+                o = 0.;
+                e = 0.;
+            }
+            k += 1;
+        }
+        // everything from `k` upwards
+        o += (sorted.len() - i) as f64;
+        e += n * (1. - cum).max(0.);
+        if e < 5. && !groups.is_empty() {
+            let last = groups.len() - 1;
+            groups[last].0 += o;
+            groups[last].1 += e;
+        } else {
+            groups.push((o, e));
+        }
+        if groups.len() < 2 {
+            return;
+        }
+
+        let chi2: f64 = groups.iter().map(|&(o, e)| (o - e) * (o - e) / e).sum();
+        // Wilson-Hilferty approximation of the 0.999 quantile of the
+        // chi-squared distribution with `df` degrees of freedom
+        let df = (groups.len() - 1) as f64;
+        let h = 2. / (9. * df);
+        let critical = df * (1. - h + 3.09 * h.sqrt()).powi(3);
+        assert!(chi2 < critical,
+                "chi-squared statistic {} exceeds critical value {} ({} groups)",
+                chi2, critical, groups.len());
+    }
+
+    /// Assert that samples of `distr` fit its quantile function `quantile`
+    /// (the inverse of the CDF): for several probabilities `p`, the fraction
+    /// of samples not exceeding `quantile(p)` must be within six standard
+    /// errors of `p`.
+    #[cfg(feature="std")]
+    pub fn assert_quantiles<F, D, Q>(distr: D, quantile: Q)
+        where F: Into<f64>, D: Distribution<F>, Q: Fn(f64) -> f64
+    {
+        let mut rng = ::test::rng();
+        let n = 10_000;
+        let samples: Vec<f64> = (0..n).map(|_| distr.sample(&mut rng).into()).collect();
+        for &p in [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99].iter() {
+            let q = quantile(p);
+            let fraction = samples.iter().filter(|&&x| x <= q).count() as f64 / n as f64;
+            let err = 6. * (p * (1. - p) / n as f64).sqrt();
+            assert!((fraction - p).abs() < err,
+                    "{} of samples are at most the {} quantile {}", fraction, p, q);
+        }
+    }
+
+    #[test]
+    #[cfg(feature="std")]
+    fn test_log_gamma() {
+        use super::log_gamma;
+        // (x, ln(gamma(x)))
+        let values = [(0.5, 0.5723649429247004), (1.0, 0.0), (1.5, -0.12078223763524543),
+                      (2.0, 0.0), (3.0, 2.0f64.ln()), (10.0, 362880f64.ln()),
+                      (100.5, 361.4355404677776), (1e6, 12815504.569147611)];
+        for &(x, expected) in values.iter() {
+            let got = log_gamma(x);
+            assert!((got - expected).abs() <= 1e-12 * expected.abs().max(1.),
+                    "log_gamma({}) = {}, expected {}", x, got, expected);
+        }
+    }
 
     #[test]
     fn test_fn_weighted_bool() {
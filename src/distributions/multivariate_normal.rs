@@ -0,0 +1,250 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The multivariate normal distribution.
+
+use Rng;
+use distributions::Distribution;
+use distributions::normal::standard_normal;
+
+/// The multivariate normal distribution `N(mean, covariance)`.
+///
+/// The covariance matrix is factorised as `L L^T` with `L` lower triangular
+/// (the Cholesky decomposition) once, at construction. A sample is then
+/// `mean + L z`, where `z` is a vector of independent `standard_normal`
+/// samples.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{MultivariateNormal, Distribution};
+///
+/// let mvn = MultivariateNormal::new(vec![1.0, 2.0],
+///                                   vec![vec![1.0, 0.5], vec![0.5, 2.0]]);
+/// let v = mvn.sample(&mut rand::thread_rng());
+/// println!("{:?} is from a bivariate normal distribution", v);
+///
+/// // sample into an existing buffer, without allocating
+/// let mut buf = [0.0; 2];
+/// mvn.sample_into(&mut rand::thread_rng(), &mut buf);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultivariateNormal {
+    mean: Vec<f64>,
+    /// The Cholesky factor `L`, packed by rows: row `i` holds the `i + 1`
+    /// elements `L[i][0..=i]`, starting at index `i * (i + 1) / 2`.
+    chol: Vec<f64>,
+}
+
+parameter_error! {
+    /// An error constructing a `MultivariateNormal`.
+    pub enum MultivariateNormalError {
+        /// The covariance matrix is not square, or its dimension differs from
+        /// that of the mean.
+        DimensionMismatch => "covariance matrix dimensions do not match the mean",
+        /// The covariance matrix is not symmetric.
+        NotSymmetric => "covariance matrix is not symmetric",
+        /// The covariance matrix is not positive definite (or contains
+        /// non-finite values).
+        NotPositiveDefinite => "covariance matrix is not positive definite",
+        /// The mean contains a NaN or infinite value.
+        MeanNotFinite => "mean is NaN or infinite",
+    }
+}
+
+impl MultivariateNormal {
+    /// Construct a new `MultivariateNormal` with the given `mean` vector and
+    /// `covariance` matrix, given as a vector of rows.
+    ///
+    /// Panics if the parameters are invalid; see `try_new`.
+    pub fn new(mean: Vec<f64>, covariance: Vec<Vec<f64>>) -> MultivariateNormal {
+        MultivariateNormal::try_new(mean, covariance)
+            .unwrap_or_else(|e| panic!("MultivariateNormal::new: {}", e))
+    }
+
+    /// Construct a new `MultivariateNormal` with the given `mean` vector and
+    /// `covariance` matrix, or return an error if the parameters are invalid.
+    ///
+    /// Returns an error unless `covariance` is a symmetric positive definite
+    /// `n` by `n` matrix, where `n` is the length of `mean`. Symmetry is
+    /// checked up to a relative tolerance of `1e-12`, to allow for rounding
+    /// in computed matrices; only the lower triangle is used. NaN or
+    /// infinite values give `MeanNotFinite` in `mean`, and
+    /// `NotPositiveDefinite` in `covariance`.
+    pub fn try_new(mean: Vec<f64>, covariance: Vec<Vec<f64>>)
+        -> Result<MultivariateNormal, MultivariateNormalError>
+    {
+        let n = mean.len();
+        if covariance.len() != n || covariance.iter().any(|row| row.len() != n) {
+            return Err(MultivariateNormalError::DimensionMismatch);
+        }
+        if !mean.iter().all(|x| x.is_finite()) {
+            return Err(MultivariateNormalError::MeanNotFinite);
+        }
+        for (i, row) in covariance.iter().enumerate() {
+            for (j, &a) in row[..i].iter().enumerate() {
+                let b = covariance[j][i];
+                if (a - b).abs() > 1e-12 * a.abs().max(b.abs()) {
+                    return Err(MultivariateNormalError::NotSymmetric);
+                }
+            }
+        }
+
+        // Cholesky–Banachiewicz: compute L row by row
+        let mut chol = vec![0.0; n * (n + 1) / 2];
+        for (i, cov_row) in covariance.iter().enumerate() {
+            let row_i = i * (i + 1) / 2;
+            for j in 0..(i + 1) {
+                let row_j = j * (j + 1) / 2;
+                let mut sum = cov_row[j];
+                for k in 0..j {
+                    sum -= chol[row_i + k] * chol[row_j + k];
+                }
+                if i == j {
+                    if sum.is_nan() || sum <= 0.0 {
+                        return Err(MultivariateNormalError::NotPositiveDefinite);
+                    }
+                    chol[row_i + i] = sum.sqrt();
+                } else {
+                    chol[row_i + j] = sum / chol[row_j + j];
+                }
+            }
+        }
+        if chol.iter().any(|x| !x.is_finite()) {
+            return Err(MultivariateNormalError::NotPositiveDefinite);
+        }
+
+        Ok(MultivariateNormal { mean, chol })
+    }
+
+    /// The dimension of the distribution.
+    pub fn dim(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Generate a sample into `dest`, without allocating.
+    ///
+    /// Panics if the length of `dest` differs from the dimension.
+    pub fn sample_into<R: Rng+?Sized>(&self, rng: &mut R, dest: &mut [f64]) {
+        let n = self.dim();
+        assert_eq!(dest.len(), n,
+                   "MultivariateNormal::sample_into called with a slice of the wrong length");
+        for z in dest.iter_mut() {
+            *z = standard_normal(rng);
+        }
+        // Compute `mean + L z` in place: row `i` of `L` only uses `z[..=i]`,
+        // so working upwards from the last row leaves the `z` values still
+        // needed untouched.
+        for i in (0..n).rev() {
+            let row = &self.chol[i * (i + 1) / 2..][..i + 1];
+            let x: f64 = row.iter().zip(dest.iter()).map(|(l, z)| l * z).sum();
+            dest[i] = self.mean[i] + x;
+        }
+    }
+}
+
+impl Distribution<Vec<f64>> for MultivariateNormal {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let mut result = vec![0.0; self.dim()];
+        self.sample_into(rng, &mut result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{MultivariateNormal, MultivariateNormalError};
+
+    #[test]
+    fn test_cholesky() {
+        let mvn = MultivariateNormal::new(
+            vec![0.0; 3],
+            vec![vec![4.0, 12.0, -16.0], vec![12.0, 37.0, -43.0], vec![-16.0, -43.0, 98.0]],
+        );
+        assert_eq!(mvn.chol, vec![2.0, 6.0, 1.0, -8.0, 5.0, 3.0]);
+    }
+
+    #[test]
+    fn test_moments() {
+        let mean = vec![1.0, -2.0, 10.0];
+        let cov = vec![vec![2.0, 0.6, -0.3], vec![0.6, 1.0, 0.2], vec![-0.3, 0.2, 0.5]];
+        let mvn = MultivariateNormal::new(mean.clone(), cov.clone());
+        assert_eq!(mvn.dim(), 3);
+
+        let mut rng = ::test::rng();
+        let n = 20_000;
+        let samples: Vec<Vec<f64>> = (0..n).map(|_| mvn.sample(&mut rng)).collect();
+        for i in 0..3 {
+            let m = samples.iter().map(|x| x[i]).sum::<f64>() / n as f64;
+            assert!((m - mean[i]).abs() < 6. * (cov[i][i] / n as f64).sqrt(),
+                    "mean {} of component {}", m, i);
+        }
+        for i in 0..3 {
+            for j in 0..3 {
+                let c = samples.iter().map(|x| (x[i] - mean[i]) * (x[j] - mean[j]))
+                    .sum::<f64>() / n as f64;
+                // Var(X_i X_j) = cov_ii cov_jj + cov_ij^2 for centred normals
+                let se = ((cov[i][i] * cov[j][j] + cov[i][j] * cov[i][j]) / n as f64).sqrt();
+                assert!((c - cov[i][j]).abs() < 6. * se,
+                        "covariance {} of components {}, {}", c, i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_into() {
+        let mvn = MultivariateNormal::new(vec![5.0, 5.0], vec![vec![1e-20, 0.0], vec![0.0, 1e-20]]);
+        let mut buf = [0.0; 2];
+        mvn.sample_into(&mut ::test::rng(), &mut buf);
+        assert!((buf[0] - 5.0).abs() < 1e-6 && (buf[1] - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_into_wrong_length() {
+        let mvn = MultivariateNormal::new(vec![0.0, 0.0], vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        mvn.sample_into(&mut ::test::rng(), &mut [0.0; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_not_symmetric() {
+        MultivariateNormal::new(vec![0.0; 2], vec![vec![1.0, 0.5], vec![0.4, 1.0]]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(MultivariateNormal::try_new(vec![0.0; 2], vec![vec![1.0, 0.0]])
+                       .unwrap_err(),
+                   MultivariateNormalError::DimensionMismatch);
+        assert_eq!(MultivariateNormal::try_new(vec![0.0; 2], vec![vec![1.0], vec![0.0, 1.0]])
+                       .unwrap_err(),
+                   MultivariateNormalError::DimensionMismatch);
+        assert_eq!(MultivariateNormal::try_new(vec![0.0; 2], vec![vec![1.0, 0.5], vec![0.4, 1.0]])
+                       .unwrap_err(),
+                   MultivariateNormalError::NotSymmetric);
+        // eigenvalues 3 and -1
+        assert_eq!(MultivariateNormal::try_new(vec![0.0; 2], vec![vec![1.0, 2.0], vec![2.0, 1.0]])
+                       .unwrap_err(),
+                   MultivariateNormalError::NotPositiveDefinite);
+        // singular
+        assert_eq!(MultivariateNormal::try_new(vec![0.0; 2], vec![vec![1.0, 1.0], vec![1.0, 1.0]])
+                       .unwrap_err(),
+                   MultivariateNormalError::NotPositiveDefinite);
+        let nan = ::std::f64::NAN;
+        assert_eq!(MultivariateNormal::try_new(vec![0.0; 2], vec![vec![1.0, nan], vec![nan, 1.0]])
+                       .unwrap_err(),
+                   MultivariateNormalError::NotPositiveDefinite);
+        assert_eq!(MultivariateNormal::try_new(vec![nan, 0.0], vec![vec![1.0, 0.0], vec![0.0, 1.0]])
+                       .unwrap_err(),
+                   MultivariateNormalError::MeanNotFinite);
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The negative binomial distribution.
+
+use Rng;
+use distributions::{Distribution, Gamma, Poisson};
+
+/// The negative binomial distribution `NegativeBinomial(r, p)`: the number of
+/// failures before the `r`-th success in a series of Bernoulli trials, each
+/// succeeding with probability `p`.
+///
+/// This distribution has probability mass function:
+/// `f(k) = Γ(k + r) / (k! Γ(r)) * p^r * (1 - p)^k` for `k >= 0`,
+/// where `r` need not be an integer.
+///
+/// Samples are generated as a Gamma–Poisson mixture: `Poisson(λ)` where `λ`
+/// follows `Gamma(r, (1 - p) / p)`.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{NegativeBinomial, Distribution};
+///
+/// let nb = NegativeBinomial::new(3.0, 0.4);
+/// let v = nb.sample(&mut rand::thread_rng());
+/// println!("{} is from a NegativeBinomial(3, 0.4) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct NegativeBinomial {
+    /// `Gamma(r, 1)`, scaled by `odds` to give the Poisson mean.
+    gamma: Gamma,
+    /// `(1 - p) / p`, which is zero for `p == 1` and may overflow to
+    /// infinity for tiny `p`.
+    odds: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `NegativeBinomial` distribution.
+    pub enum NegativeBinomialError {
+        /// `r` is NaN or infinite.
+        RNotFinite => "`r` is NaN or infinite",
+        /// `r <= 0`.
+        RNotPositive => "`r` is not positive",
+        /// `p` is NaN or outside `(0, 1]`.
+        ProbabilityOutOfRange => "`p` is not in (0, 1]",
+    }
+}
+
+impl NegativeBinomial {
+    /// Construct a new `NegativeBinomial` with the given number of successes
+    /// `r` and probability of success `p`. Panics if `r` is not finite and
+    /// positive, or `p` is not in `(0, 1]`; see `try_new`.
+    pub fn new(r: f64, p: f64) -> NegativeBinomial {
+        NegativeBinomial::try_new(r, p)
+            .unwrap_or_else(|e| panic!("NegativeBinomial::new: {}", e))
+    }
+
+    /// Construct a new `NegativeBinomial`, or return an error if `r` is NaN
+    /// or infinite (`RNotFinite`), `r <= 0` (`RNotPositive`), or `p` is NaN
+    /// or not in `(0, 1]` (`ProbabilityOutOfRange`).
+    pub fn try_new(r: f64, p: f64) -> Result<NegativeBinomial, NegativeBinomialError> {
+        if !r.is_finite() {
+            return Err(NegativeBinomialError::RNotFinite);
+        } else if r <= 0.0 {
+            return Err(NegativeBinomialError::RNotPositive);
+        } else if !(p > 0.0 && p <= 1.0) {
+            return Err(NegativeBinomialError::ProbabilityOutOfRange);
+        }
+        Ok(NegativeBinomial { gamma: Gamma::new(r, 1.0), odds: (1. - p) / p })
+    }
+}
+
+impl Distribution<u64> for NegativeBinomial {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        let lambda = self.gamma.sample(rng) * self.odds;
+        if lambda.is_infinite() {
+            return ::std::u64::MAX;
+        }
+        // lambda is zero if p == 1, and may underflow to zero for small r
+        // (or be NaN if that coincides with infinite odds)
+        if lambda > 0.0 {
+            Poisson::new(lambda).sample(rng)
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use SeedableRng;
+    use distributions::{log_gamma, Distribution};
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{NegativeBinomial, NegativeBinomialError};
+
+    #[test]
+    fn test_negative_binomial_fit() {
+        for &(r, p) in [(1.0, 0.5), (3.0, 0.4), (0.5, 0.1), (25.5, 0.9), (100.0, 0.2)].iter() {
+            let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+            let nb = NegativeBinomial::new(r, p);
+            let samples: Vec<u64> = (0..20_000).map(|_| nb.sample(&mut rng)).collect();
+            assert_discrete_fit(&samples, |k| {
+                let k = k as f64;
+                (log_gamma(k + r) - log_gamma(k + 1.) - log_gamma(r)
+                    + r * p.ln() + k * (-p).ln_1p()).exp()
+            });
+        }
+    }
+
+    #[test]
+    fn test_negative_binomial_p_one() {
+        let mut rng = ::test::rng();
+        assert_eq!(NegativeBinomial::new(5.0, 1.0).sample(&mut rng), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_binomial_invalid_r() {
+        NegativeBinomial::new(0.0, 0.5);
+    }
+    #[test]
+    #[should_panic]
+    fn test_negative_binomial_invalid_p() {
+        NegativeBinomial::new(1.0, 0.0);
+    }
+    #[test]
+    fn test_negative_binomial_try_new() {
+        assert!(NegativeBinomial::try_new(2.0, 1.0).is_ok());
+        assert_eq!(NegativeBinomial::try_new(f64::NAN, 0.5).unwrap_err(),
+                   NegativeBinomialError::RNotFinite);
+        assert_eq!(NegativeBinomial::try_new(0.0, 0.5).unwrap_err(),
+                   NegativeBinomialError::RNotPositive);
+        for &p in [0.0, 1.5, f64::NAN].iter() {
+            assert_eq!(NegativeBinomial::try_new(2.0, p).unwrap_err(),
+                       NegativeBinomialError::ProbabilityOutOfRange);
+        }
+    }
+
+    #[test]
+    fn test_negative_binomial_tiny_p() {
+        let mut rng = ::test::rng();
+        let nb = NegativeBinomial::new(1.0, 1e-320);
+        for _ in 0..100 {
+            nb.sample(&mut rng);
+        }
+    }
+}
@@ -11,7 +11,7 @@
 //! The normal and derived distributions.
 
 use {Rng};
-use distributions::{ziggurat, ziggurat_tables, Distribution, Rand, Open01};
+use distributions::{ziggurat, Distribution, Float};
 
 /// Generates N(0, 1) random numbers
 /// (a.k.a.  a standard normal, or Gaussian).
@@ -34,37 +34,43 @@ use distributions::{ziggurat, ziggurat_tables, Distribution, Rand, Open01};
 /// println!("{}", x);
 /// ```
 pub fn standard_normal<R:Rng+?Sized>(rng: &mut R) -> f64 {
+    standard_normal_float(rng)
+}
+
+/// Generates N(0, 1) random numbers of any `Float` type; `standard_normal`
+/// is the `f64` version.
+pub(crate) fn standard_normal_float<F: Float, R:Rng+?Sized>(rng: &mut R) -> F {
     #[inline]
-    fn pdf(x: f64) -> f64 {
-        (-x*x/2.0).exp()
+    fn pdf<F: Float>(x: F) -> F {
+        (-x*x/F::from_f64(2.0)).exp()
     }
     #[inline]
-    fn zero_case<R:Rng+?Sized>(rng: &mut R, u: f64) -> f64 {
+    fn zero_case<F: Float, R:Rng+?Sized>(rng: &mut R, u: F) -> F {
         // compute a random number in the tail by hand
 
         // strange initial conditions, because the loop is not
         // do-while, so the condition should be true on the first
         // run, they get overwritten anyway (0 < 1, so these are
         // good).
-        let mut x = 1.0f64;
-        let mut y = 0.0f64;
+        let mut x = F::from_f64(1.0);
+        let mut y = F::from_f64(0.0);
 
-        while -2.0 * y < x * x {
-            let x_ = f64::rand(rng, Open01);
-            let y_ = f64::rand(rng, Open01);
+        while F::from_f64(-2.0) * y < x * x {
+            let x_ = F::open01(rng);
+            let y_ = F::open01(rng);
 
-            x = x_.ln() / ziggurat_tables::ZIG_NORM_R;
+            x = x_.ln() / F::ZIG_NORM_R;
             y = y_.ln();
         }
 
-        if u < 0.0 { x - ziggurat_tables::ZIG_NORM_R } else { ziggurat_tables::ZIG_NORM_R - x }
+        if u < F::from_f64(0.0) { x - F::ZIG_NORM_R } else { F::ZIG_NORM_R - x }
     }
 
     ziggurat(
         rng,
         true, // this is symmetric
-        &ziggurat_tables::ZIG_NORM_X,
-        &ziggurat_tables::ZIG_NORM_F,
+        F::zig_norm_x(),
+        F::zig_norm_f(),
         pdf, zero_case)
 }
 
@@ -81,33 +87,68 @@ pub fn standard_normal<R:Rng+?Sized>(rng: &mut R) -> f64 {
 /// // mean 2, standard deviation 3
 /// let normal = Normal::new(2.0, 3.0);
 /// let v = normal.sample(&mut rand::thread_rng());
-/// println!("{} is from a N(2, 9) distribution", v)
+/// println!("{} is from a N(2, 9) distribution", v);
+///
+/// // the same, sampling `f32` values
+/// let normal = Normal::new(2.0f32, 3.0);
+/// let v: f32 = normal.sample(&mut rand::thread_rng());
+/// println!("{} is from a N(2, 9) distribution", v);
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct Normal {
-    mean: f64,
-    std_dev: f64,
+pub struct Normal<F = f64> {
+    mean: F,
+    std_dev: F,
+}
+
+parameter_error! {
+    /// An error constructing a `Normal` or `LogNormal` distribution.
+    pub enum NormalError {
+        /// `mean` is NaN or infinite.
+        MeanNotFinite => "`mean` is NaN or infinite",
+        /// `std_dev` is NaN or infinite.
+        StdDevNotFinite => "`std_dev` is NaN or infinite",
+        /// `std_dev < 0`.
+        StdDevNegative => "`std_dev` is negative",
+    }
 }
 
-impl Normal {
+impl<F: Float> Normal<F> {
     /// Construct a new `Normal` distribution with the given mean and
     /// standard deviation.
     ///
     /// # Panics
     ///
-    /// Panics if `std_dev < 0`.
+    /// Panics if `mean` or `std_dev` is not finite, or `std_dev < 0`; see
+    /// `try_new`.
     #[inline]
-    pub fn new(mean: f64, std_dev: f64) -> Normal {
-        assert!(std_dev >= 0.0, "Normal::new called with `std_dev` < 0");
-        Normal {
+    pub fn new(mean: F, std_dev: F) -> Normal<F> {
+        Normal::try_new(mean, std_dev).unwrap_or_else(|e| panic!("Normal::new: {}", e))
+    }
+
+    /// Construct a new `Normal` distribution with the given mean and
+    /// standard deviation, or return an error if the parameters are invalid.
+    ///
+    /// `mean` must be finite, giving `MeanNotFinite` if it is NaN or
+    /// infinite. `std_dev` must be finite and non-negative, giving
+    /// `StdDevNotFinite` if it is NaN or infinite and `StdDevNegative` if it
+    /// is below zero; a `std_dev` of zero always samples `mean`.
+    pub fn try_new(mean: F, std_dev: F) -> Result<Normal<F>, NormalError> {
+        if !mean.is_finite() {
+            return Err(NormalError::MeanNotFinite);
+        } else if !std_dev.is_finite() {
+            return Err(NormalError::StdDevNotFinite);
+        } else if std_dev < F::from_f64(0.0) {
+            return Err(NormalError::StdDevNegative);
+        }
+        Ok(Normal {
             mean: mean,
             std_dev: std_dev
-        }
+        })
     }
 }
-impl Distribution<f64> for Normal {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
-        self.mean + self.std_dev * standard_normal(rng)
+impl<F: Float> Distribution<F> for Normal<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
+        self.mean + self.std_dev * standard_normal_float(rng)
     }
 }
 
@@ -128,33 +169,55 @@ impl Distribution<f64> for Normal {
 /// println!("{} is from an ln N(2, 9) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
-pub struct LogNormal {
-    norm: Normal
+pub struct LogNormal<F = f64> {
+    norm: Normal<F>
 }
 
-impl LogNormal {
+impl<F: Float> LogNormal<F> {
     /// Construct a new `LogNormal` distribution with the given mean
     /// and standard deviation.
     ///
     /// # Panics
     ///
-    /// Panics if `std_dev < 0`.
+    /// Panics if `mean` or `std_dev` is not finite, or `std_dev < 0`; see
+    /// `try_new`.
     #[inline]
-    pub fn new(mean: f64, std_dev: f64) -> LogNormal {
-        assert!(std_dev >= 0.0, "LogNormal::new called with `std_dev` < 0");
-        LogNormal { norm: Normal::new(mean, std_dev) }
+    pub fn new(mean: F, std_dev: F) -> LogNormal<F> {
+        LogNormal::try_new(mean, std_dev)
+            .unwrap_or_else(|e| panic!("LogNormal::new: {}", e))
+    }
+
+    /// Construct a new `LogNormal` distribution with the given mean and
+    /// standard deviation of the underlying normal distribution, or return
+    /// an error under the same conditions as `Normal::try_new`.
+    pub fn try_new(mean: F, std_dev: F) -> Result<LogNormal<F>, NormalError> {
+        Normal::try_new(mean, std_dev).map(|norm| LogNormal { norm })
     }
 }
-impl Distribution<f64> for LogNormal {
-    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+impl<F: Float> Distribution<F> for LogNormal<F> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> F {
         self.norm.sample(rng).exp()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::f64;
     use distributions::{Distribution};
-    use super::{Normal, LogNormal};
+    use distributions::test::assert_quantiles;
+    use super::{Normal, LogNormal, NormalError};
+
+    /// The quantiles of the standard normal distribution at the
+    /// probabilities checked by `assert_quantiles`.
+    fn standard_quantile(p: f64) -> f64 {
+        let table = [(0.01, -2.3263478740408408), (0.1, -1.2815515655446004),
+                     (0.25, -0.6744897501960817), (0.5, 0.0)];
+        for &(q, z) in table.iter() {
+            if p == q { return z; }
+            if p == 1. - q { return -z; }
+        }
+        panic!("no quantile for {}", p)
+    }
 
     #[test]
     fn test_normal() {
@@ -165,10 +228,26 @@ mod tests {
         }
     }
     #[test]
+    fn test_normal_quantiles() {
+        assert_quantiles(Normal::new(10.0, 3.0), |p| 10.0 + 3.0 * standard_quantile(p));
+        assert_quantiles(Normal::new(10.0f32, 3.0), |p| 10.0 + 3.0 * standard_quantile(p));
+        assert_quantiles(LogNormal::new(1.0f32, 0.5),
+                         |p| (1.0 + 0.5 * standard_quantile(p)).exp());
+    }
+    #[test]
     #[should_panic]
     fn test_normal_invalid_sd() {
         Normal::new(10.0, -1.0);
     }
+    #[test]
+    fn test_normal_try_new() {
+        assert!(Normal::try_new(10.0, 0.0).is_ok());
+        assert_eq!(Normal::try_new(f64::NAN, 1.0).unwrap_err(), NormalError::MeanNotFinite);
+        assert_eq!(Normal::try_new(f64::NEG_INFINITY, 1.0).unwrap_err(),
+                   NormalError::MeanNotFinite);
+        assert_eq!(Normal::try_new(0.0, f64::NAN).unwrap_err(), NormalError::StdDevNotFinite);
+        assert_eq!(Normal::try_new(0.0, -1.0).unwrap_err(), NormalError::StdDevNegative);
+    }
 
 
     #[test]
@@ -184,4 +263,9 @@ mod tests {
     fn test_log_normal_invalid_sd() {
         LogNormal::new(10.0, -1.0);
     }
+    #[test]
+    fn test_log_normal_try_new() {
+        assert_eq!(LogNormal::try_new(0.0, f64::INFINITY).unwrap_err(),
+                   NormalError::StdDevNotFinite);
+    }
 }
@@ -0,0 +1,125 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Pareto distribution.
+
+use Rng;
+use distributions::{Distribution, Open01, Rand};
+
+/// The Pareto distribution `Pareto(scale, shape)`.
+///
+/// This distribution has density function:
+/// `f(x) = shape * scale^shape / x^(shape + 1)` for `x >= scale`.
+///
+/// Samples are generated by inversion of the CDF.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Pareto, Distribution};
+///
+/// let pareto = Pareto::new(1.0, 2.0);
+/// let v = pareto.sample(&mut rand::thread_rng());
+/// println!("{} is from a Pareto(1, 2) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Pareto {
+    scale: f64,
+    inv_neg_shape: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Pareto` distribution.
+    pub enum ParetoError {
+        /// `scale` is NaN or infinite.
+        ScaleNotFinite => "`scale` is NaN or infinite",
+        /// `scale <= 0`.
+        ScaleNotPositive => "`scale` is not positive",
+        /// `shape` is NaN or infinite.
+        ShapeNotFinite => "`shape` is NaN or infinite",
+        /// `shape <= 0`.
+        ShapeNotPositive => "`shape` is not positive",
+    }
+}
+
+impl Pareto {
+    /// Construct a new `Pareto` with the given `scale` (the minimum value)
+    /// and `shape` parameters. Panics if either is not finite and positive;
+    /// see `try_new`.
+    pub fn new(scale: f64, shape: f64) -> Pareto {
+        Pareto::try_new(scale, shape).unwrap_or_else(|e| panic!("Pareto::new: {}", e))
+    }
+
+    /// Construct a new `Pareto`, or return an error if `scale` or `shape`
+    /// is NaN or infinite (`ScaleNotFinite`, `ShapeNotFinite`) or `<= 0`
+    /// (`ScaleNotPositive`, `ShapeNotPositive`).
+    pub fn try_new(scale: f64, shape: f64) -> Result<Pareto, ParetoError> {
+        if !scale.is_finite() {
+            return Err(ParetoError::ScaleNotFinite);
+        } else if scale <= 0.0 {
+            return Err(ParetoError::ScaleNotPositive);
+        } else if !shape.is_finite() {
+            return Err(ParetoError::ShapeNotFinite);
+        } else if shape <= 0.0 {
+            return Err(ParetoError::ShapeNotPositive);
+        }
+        Ok(Pareto { scale, inv_neg_shape: -1. / shape })
+    }
+}
+
+impl Distribution<f64> for Pareto {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+        let u = f64::rand(rng, Open01);
+        self.scale * u.powf(self.inv_neg_shape)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::{Distribution};
+    use std::f64;
+    use distributions::test::assert_quantiles;
+    use super::{Pareto, ParetoError};
+
+    #[test]
+    fn test_pareto_quantiles() {
+        for &(scale, shape) in [(1.0, 1.0), (1.0, 2.0), (10.0, 0.5), (0.1, 30.0)].iter() {
+            assert_quantiles(Pareto::new(scale, shape),
+                             |p| scale * (1. - p).powf(-1. / shape));
+        }
+    }
+
+    #[test]
+    fn test_pareto_minimum() {
+        let pareto = Pareto::new(3.0, 5.0);
+        let mut rng = ::test::rng();
+        for _ in 0..1000 {
+            assert!(pareto.sample(&mut rng) >= 3.0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pareto_invalid_scale() {
+        Pareto::new(0.0, 1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_pareto_invalid_shape() {
+        Pareto::new(1.0, -1.0);
+    }
+    #[test]
+    fn test_pareto_try_new() {
+        assert_eq!(Pareto::try_new(f64::INFINITY, 1.0).unwrap_err(),
+                   ParetoError::ScaleNotFinite);
+        assert_eq!(Pareto::try_new(-1.0, 1.0).unwrap_err(), ParetoError::ScaleNotPositive);
+        assert_eq!(Pareto::try_new(1.0, f64::NAN).unwrap_err(), ParetoError::ShapeNotFinite);
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Poisson distribution.
+
+use Rng;
+use distributions::{log_gamma, Distribution, Uniform01, Rand};
+use distributions::exponential::exp1;
+
+/// The Poisson distribution `Poisson(lambda)`.
+///
+/// This distribution has probability mass function:
+/// `f(k) = lambda^k * exp(-lambda) / k!` for `k >= 0`.
+///
+/// For `lambda < 10`, samples are generated by counting the arrivals of a
+/// Poisson process with unit rate in an interval of length `lambda`, using
+/// `exp1` for the inter-arrival times. For larger `lambda` the PTRS
+/// transformed rejection method[1] is used.
+///
+/// [1]: Wolfgang Hörmann (1993). [*The transformed rejection method for
+/// generating Poisson random
+/// variables*](https://doi.org/10.1016/0167-6687(93)90997-4). Insurance:
+/// Mathematics and Economics 12, 1, 39-45.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Poisson, Distribution};
+///
+/// let poi = Poisson::new(2.0);
+/// let v = poi.sample(&mut rand::thread_rng());
+/// println!("{} is from a Poisson(2) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Poisson {
+    repr: PoissonRepr,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PoissonRepr {
+    Small(f64),
+    Large(PoissonPtrs),
+}
+
+/// Constants of the PTRS algorithm for a given `lambda`.
+#[derive(Clone, Copy, Debug)]
+struct PoissonPtrs {
+    lambda: f64,
+    ln_lambda: f64,
+    a: f64,
+    b: f64,
+    ln_inv_alpha: f64,
+    v_r: f64,
+}
+
+/// Below this value of `lambda` arrivals are counted rather than using PTRS.
+const PTRS_THRESHOLD: f64 = 10.;
+
+parameter_error! {
+    /// An error constructing a `Poisson` distribution.
+    pub enum PoissonError {
+        /// `lambda` is NaN or infinite.
+        LambdaNotFinite => "`lambda` is NaN or infinite",
+        /// `lambda <= 0`.
+        LambdaNotPositive => "`lambda` is not positive",
+    }
+}
+
+impl Poisson {
+    /// Construct a new `Poisson` with the given shape parameter
+    /// `lambda`. Panics if `lambda` is not finite and positive; see
+    /// `try_new`.
+    pub fn new(lambda: f64) -> Poisson {
+        Poisson::try_new(lambda).unwrap_or_else(|e| panic!("Poisson::new: {}", e))
+    }
+
+    /// Construct a new `Poisson` with the given shape parameter `lambda`,
+    /// or return an error if `lambda` is NaN or infinite (`LambdaNotFinite`)
+    /// or `lambda <= 0` (`LambdaNotPositive`).
+    pub fn try_new(lambda: f64) -> Result<Poisson, PoissonError> {
+        if !lambda.is_finite() {
+            return Err(PoissonError::LambdaNotFinite);
+        } else if lambda <= 0.0 {
+            return Err(PoissonError::LambdaNotPositive);
+        }
+        let repr = if lambda < PTRS_THRESHOLD {
+            PoissonRepr::Small(lambda)
+        } else {
+            PoissonRepr::Large(PoissonPtrs::new(lambda))
+        };
+        Ok(Poisson { repr })
+    }
+}
+
+impl PoissonPtrs {
+    fn new(lambda: f64) -> PoissonPtrs {
+        let b = 0.931 + 2.53 * lambda.sqrt();
+        PoissonPtrs {
+            lambda,
+            ln_lambda: lambda.ln(),
+            a: -0.059 + 0.02483 * b,
+            b,
+            ln_inv_alpha: (1.1239 + 1.1328 / (b - 3.4)).ln(),
+            v_r: 0.9277 - 3.6224 / (b - 2.),
+        }
+    }
+}
+
+impl Distribution<u64> for Poisson {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        match self.repr {
+            PoissonRepr::Small(lambda) => {
+                // count the arrivals up to time `lambda`
+                let mut k = 0;
+                let mut t = exp1(rng);
+                while t <= lambda {
+                    k += 1;
+                    t += exp1(rng);
+                }
+                k
+            }
+            PoissonRepr::Large(ref ptrs) => ptrs.sample(rng),
+        }
+    }
+}
+
+impl Distribution<u64> for PoissonPtrs {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        loop {
+            let u = f64::rand(rng, Uniform01) - 0.5;
+            let v = f64::rand(rng, Uniform01);
+            let us = 0.5 - u.abs();
+            let k = ((2. * self.a / us + self.b) * u + self.lambda + 0.43).floor();
+
+            // fast acceptance in the centre of the distribution
+            if us >= 0.07 && v <= self.v_r {
+                return k as u64;
+            }
+            if k < 0. || (us < 0.013 && v > us) {
+                continue;
+            }
+            let lhs = v.ln() + self.ln_inv_alpha - (self.a / (us * us) + self.b).ln();
+            let rhs = -self.lambda + k * self.ln_lambda - log_gamma(k + 1.);
+            if lhs <= rhs {
+                return k as u64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use SeedableRng;
+    use distributions::{log_gamma, Distribution};
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{Poisson, PoissonError};
+
+    fn test_moments(lambda: f64) {
+        let mut rng = ::test::rng();
+        let poisson = Poisson::new(lambda);
+        let n = 10_000;
+        let samples: Vec<f64> = (0..n).map(|_| poisson.sample(&mut rng) as f64).collect();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+            / (n - 1) as f64;
+        // Both the mean and variance are lambda. Allow for six standard
+        // errors: the variance of the sample variance is about
+        // (2 * lambda^2 + lambda) / n.
+        let mean_err = 6. * (lambda / n as f64).sqrt();
+        let var_err = 6. * ((2. * lambda * lambda + lambda) / n as f64).sqrt();
+        assert!((mean - lambda).abs() < mean_err, "mean {} for lambda {}", mean, lambda);
+        assert!((var - lambda).abs() < var_err, "variance {} for lambda {}", var, lambda);
+    }
+
+    #[test]
+    fn test_poisson_moments() {
+        for &lambda in [0.1, 1.0, 5.5, 9.9, 10.0, 25.0, 1000.0, 1e7].iter() {
+            test_moments(lambda);
+        }
+    }
+
+    #[test]
+    fn test_poisson_fit() {
+        for &lambda in [0.5, 3.0, 9.5, 12.0, 150.0, 1e5].iter() {
+            let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+            let poisson = Poisson::new(lambda);
+            let samples: Vec<u64> = (0..20_000).map(|_| poisson.sample(&mut rng)).collect();
+            assert_discrete_fit(&samples, |k| {
+                (k as f64 * lambda.ln() - lambda - log_gamma(k as f64 + 1.)).exp()
+            });
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_poisson_invalid_lambda_zero() {
+        Poisson::new(0.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_poisson_invalid_lambda_neg() {
+        Poisson::new(-10.0);
+    }
+    #[test]
+    fn test_poisson_try_new() {
+        assert_eq!(Poisson::try_new(0.0).unwrap_err(), PoissonError::LambdaNotPositive);
+        assert_eq!(Poisson::try_new(f64::NAN).unwrap_err(), PoissonError::LambdaNotFinite);
+        assert_eq!(Poisson::try_new(f64::INFINITY).unwrap_err(), PoissonError::LambdaNotFinite);
+    }
+}
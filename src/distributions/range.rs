@@ -10,6 +10,16 @@
 
 //! Generating numbers between two others.
 
+use core::cmp::Ordering;
+use core::num::Wrapping;
+use core::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroUsize};
+use core::num::{NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroIsize};
+#[cfg(feature = "i128_support")]
+use core::num::{NonZeroU128, NonZeroI128};
+use core::ops;
+#[cfg(feature="std")]
+use std::time::Duration;
+
 use Rand;
 use Rng;
 use distributions::{Distribution, Uniform};
@@ -29,6 +39,14 @@ use utils::FloatConversions;
 /// primitive integer types satisfy this property, and the float types
 /// normally satisfy it, but rounding may mean `high` can occur.
 ///
+/// Besides the primitive numbers, ranges of `char` (skipping the surrogate
+/// code points), `Duration`, `Wrapping<T>` and the `NonZero*` integer types
+/// can be sampled.
+///
+/// A `Range` can also be created from the standard range syntax with
+/// `From`: `Range::from(low..high)` is `Range::new(low, high)`, and
+/// `Range::from(low..=high)` is `Range::new_inclusive(low, high)`.
+///
 /// # Example
 ///
 /// ```rust
@@ -43,6 +61,9 @@ use utils::FloatConversions;
 ///         sum += between.sample(&mut rng);
 ///     }
 ///     println!("{}", sum);
+///
+///     let byte = Range::from(0..=255u8);
+///     let _: u8 = byte.sample(&mut rng);
 /// }
 /// ```
 #[derive(Clone, Copy, Debug)]
@@ -50,21 +71,109 @@ pub struct Range<T: RangeImpl> {
     inner: T,
 }
 
+parameter_error! {
+    /// An error constructing a `Range`.
+    pub enum RangeError {
+        /// `low >= high` for a half-open range, or `low > high` for an
+        /// inclusive one.
+        EmptyRange => "the range is empty",
+        /// The bounds cannot be compared, e.g. because one of them is NaN.
+        NanBound => "a bound is NaN",
+    }
+}
+
 // Range must be parameterised so that `Self` is fully typed, but we don't
 // actually use this type, so we just use any valid type here. (Minor lang bug?)
 impl Range<RangeInt<i32>> {
     /// Create a new `Range` instance that samples uniformly from
-    /// `[low, high)`. Panics if `low >= high`.
+    /// `[low, high)`. Panics if `low >= high` or a bound is NaN; see
+    /// `try_new`.
     pub fn new<X: SampleRange>(low: X, high: X) -> Range<X::T> {
-        assert!(low < high, "Range::new called with `low >= high`");
-        Range { inner: RangeImpl::new(low, high) }
+        Range::try_new(low, high).unwrap_or_else(|e| panic!("Range::new: {}", e))
     }
 
     /// Create a new `Range` instance that samples uniformly from
-    /// `[low, high]` (inclusive). Panics if `low >= high`.
+    /// `[low, high]` (inclusive). Panics if `low > high` or a bound is NaN;
+    /// see `try_new_inclusive`.
     pub fn new_inclusive<X: SampleRange>(low: X, high: X) -> Range<X::T> {
-        assert!(low < high, "Range::new called with `low >= high`");
-        Range { inner: RangeImpl::new_inclusive(low, high) }
+        Range::try_new_inclusive(low, high)
+            .unwrap_or_else(|e| panic!("Range::new_inclusive: {}", e))
+    }
+
+    /// Create a new `Range` instance that samples uniformly from
+    /// `[low, high)`, or return `EmptyRange` if `low >= high` and `NanBound`
+    /// if the bounds are unordered (for floats, if either is NaN).
+    ///
+    /// Infinite float bounds are not rejected, but the resulting range
+    /// samples infinite or NaN values.
+    pub fn try_new<X: SampleRange>(low: X, high: X) -> Result<Range<X::T>, RangeError> {
+        match low.partial_cmp(&high) {
+            Some(Ordering::Less) => Ok(Range { inner: RangeImpl::new(low, high) }),
+            Some(_) => Err(RangeError::EmptyRange),
+            None => Err(RangeError::NanBound),
+        }
+    }
+
+    /// Create a new `Range` instance that samples uniformly from
+    /// `[low, high]` (inclusive), or return `EmptyRange` if `low > high` and
+    /// `NanBound` if the bounds are unordered. Infinite float bounds are
+    /// handled as for `try_new`.
+    pub fn try_new_inclusive<X: SampleRange>(low: X, high: X)
+        -> Result<Range<X::T>, RangeError>
+    {
+        match low.partial_cmp(&high) {
+            Some(Ordering::Greater) => Err(RangeError::EmptyRange),
+            Some(_) => Ok(Range { inner: RangeImpl::new_inclusive(low, high) }),
+            None => Err(RangeError::NanBound),
+        }
+    }
+
+    /// Create a new `Range` instance that samples uniformly from
+    /// `[low, high)`, using the full precision of the float type.
+    ///
+    /// A `Range` created with `new` samples `low + (high - low) * x`, where
+    /// `x` is taken in fixed steps of 2^-23 for `f32` and 2^-52 for `f64`, so
+    /// a range starting at zero can not produce values below the step size.
+    /// This mode instead gets more precise near zero like the floats
+    /// themselves, using the conversions of `utils::FloatConversions`, at
+    /// some cost in speed.
+    ///
+    /// Panics if `low >= high`, or if either bound is NaN or infinite.
+    pub fn new_high_precision<X: SampleRangeFloat>(low: X, high: X) -> Range<X::T> {
+        assert!(low < high, "Range::new_high_precision called with low >= high");
+        Range { inner: X::new_high_precision(low, high, false) }
+    }
+
+    /// Create a new `Range` instance that samples uniformly from
+    /// `[low, high]` (inclusive), using the full precision of the float
+    /// type; see `new_high_precision`.
+    ///
+    /// Panics if `low > high`, or if either bound is NaN or infinite.
+    pub fn new_inclusive_high_precision<X: SampleRangeFloat>(low: X, high: X)
+        -> Range<X::T>
+    {
+        assert!(low <= high, "Range::new_inclusive_high_precision called with low > high");
+        Range { inner: X::new_high_precision(low, high, true) }
+    }
+
+    /// Sample a single value uniformly from `[low, high)`. Panics if
+    /// `low >= high`.
+    ///
+    /// This is faster than `Range::new(low, high).sample(rng)` for a one-off
+    /// sample, because it skips the setup work that makes repeated sampling
+    /// from a `Range` fast; `Sample::gen_range` uses it.
+    pub fn sample_single<X: SampleRange, R: Rng+?Sized>(low: X, high: X, rng: &mut R) -> X {
+        assert!(low < high, "Range::sample_single called with low >= high");
+        X::T::sample_single(low, high, rng)
+    }
+
+    /// Sample a single value uniformly from `[low, high]` (inclusive).
+    /// Panics if `low > high`. See `sample_single`.
+    pub fn sample_single_inclusive<X: SampleRange, R: Rng+?Sized>(low: X, high: X, rng: &mut R)
+        -> X
+    {
+        assert!(low <= high, "Range::sample_single_inclusive called with low > high");
+        X::T::sample_single_inclusive(low, high, rng)
     }
 }
 
@@ -74,6 +183,43 @@ impl<T: RangeImpl> Distribution<T::X> for Range<T> {
     }
 }
 
+impl<X: SampleRange> From<ops::Range<X>> for Range<X::T> {
+    /// Equivalent to `Range::new(range.start, range.end)`, and panics in
+    /// the same cases.
+    fn from(range: ops::Range<X>) -> Range<X::T> {
+        Range::new(range.start, range.end)
+    }
+}
+
+impl<X: SampleRange> From<ops::RangeInclusive<X>> for Range<X::T> {
+    /// Equivalent to `Range::new_inclusive(low, high)`, and panics in the
+    /// same cases.
+    fn from(range: ops::RangeInclusive<X>) -> Range<X::T> {
+        let (low, high) = range.into_inner();
+        Range::new_inclusive(low, high)
+    }
+}
+
+/// The range syntax accepted by `Sample::gen_range`: `low..high` or
+/// `low..=high`.
+pub trait SampleBounds<X> {
+    /// Sample a single value from the range. Panics if the range is empty.
+    fn sample_single<R: Rng+?Sized>(self, rng: &mut R) -> X;
+}
+
+impl<X: SampleRange> SampleBounds<X> for ops::Range<X> {
+    fn sample_single<R: Rng+?Sized>(self, rng: &mut R) -> X {
+        Range::sample_single(self.start, self.end, rng)
+    }
+}
+
+impl<X: SampleRange> SampleBounds<X> for ops::RangeInclusive<X> {
+    fn sample_single<R: Rng+?Sized>(self, rng: &mut R) -> X {
+        let (low, high) = self.into_inner();
+        Range::sample_single_inclusive(low, high, rng)
+    }
+}
+
 /// Helper trait for creating implementations of `RangeImpl`.
 pub trait SampleRange: PartialOrd+Sized {
     type T: RangeImpl<X = Self>;
@@ -86,14 +232,46 @@ pub trait RangeImpl {
 
     /// Construct self.
     ///
-    /// This should not be called directly. `Range::new` asserts that
+    /// This should not be called directly. `Range::new` checks that
     /// `low < high` before calling this.
     fn new(low: Self::X, high: Self::X) -> Self;
 
+    /// Construct self, including `high` in the range.
+    ///
+    /// This should not be called directly. `Range::new_inclusive` checks
+    /// that `low <= high` before calling this.
     fn new_inclusive(low: Self::X, high: Self::X) -> Self;
 
     /// Sample a value.
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Self::X;
+
+    /// Sample a single value from `[low, high)`.
+    ///
+    /// This should not be called directly. `Range::sample_single` checks
+    /// that `low < high` before calling this.
+    ///
+    /// Implementations can override this to skip precomputation that only
+    /// pays off when sampling repeatedly. The default implementation calls
+    /// `new` and samples from the result.
+    fn sample_single<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R)
+        -> Self::X where Self: Sized
+    {
+        let range: Self = RangeImpl::new(low, high);
+        range.sample(rng)
+    }
+
+    /// Sample a single value from `[low, high]`.
+    ///
+    /// This should not be called directly.
+    /// `Range::sample_single_inclusive` checks that `low <= high` before
+    /// calling this. The default implementation calls `new_inclusive` and
+    /// samples from the result.
+    fn sample_single_inclusive<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R)
+        -> Self::X where Self: Sized
+    {
+        let range: Self = RangeImpl::new_inclusive(low, high);
+        range.sample(rng)
+    }
 }
 
 /// Implementation of `RangeImpl` for integer types.
@@ -101,12 +279,11 @@ pub trait RangeImpl {
 pub struct RangeInt<X> {
     low: X,
     range: X,
-    zone: X,
+    ints_to_reject: X,
 }
 
 macro_rules! range_int_impl {
-    ($ty:ty, $signed:ident, $unsigned:ident,
-     $i_large:ident, $u_large:ident) => {
+    ($ty:ty, $unsigned:ident, $u_large:ident) => {
         impl SampleRange for $ty {
             type T = RangeInt<$ty>;
         }
@@ -125,18 +302,23 @@ macro_rules! range_int_impl {
 
             fn new_inclusive(low: Self::X, high: Self::X) -> Self {
                 // For a closed range the number of possible numbers we should
-                // generate is `range = (high - low + 1)`. It is not possible to
-                // end up with a uniform distribution if we map _all_ the random
-                // integers that can be generated to this range. We have to map
-                // integers from a `zone` that is a multiple of the range. The
-                // rest of the integers, that cause a bias, are rejected. The
-                // sampled number is `zone % range`.
+                // generate is `range = (high - low + 1)`. We sample with a
+                // widening multiply: a random integer `v` is multiplied by
+                // `range`, and the high word of the product is the sampled
+                // offset from `low`. This avoids a division per sample, but
+                // it is not uniform if all random integers are accepted. Each
+                // output corresponds to either `floor(2^N / range)` or one
+                // more possible values of `v`, which we can tell apart by the
+                // low word of the product. Rejecting products whose low word
+                // lies above a `zone` that leaves out `2^N % range` of the
+                // `2^N` possible low words ("Lemire's method") gives every
+                // output exactly `floor(2^N / range)` chances.
                 //
                 // The problem with `range` is that to cover the full range of
                 // the type, it has to store `unsigned_max + 1`, which can't be
                 // represented. But if the range covers the full range of the
-                // type, no modulus is needed. A range of size 0 can't exist, so
-                // we use that to represent this special case. Wrapping
+                // type, no rejection is needed. A range of size 0 can't exist,
+                // so we use that to represent this special case. Wrapping
                 // arithmetic even makes representing `unsigned_max + 1` as 0
                 // simple.
                 //
@@ -145,6 +327,8 @@ macro_rules! range_int_impl {
                 // not fitting in the type, we use:
                 // ints_to_reject = (unsigned_max + 1) % range;
                 // ints_to_reject = (unsigned_max - range + 1) % range;
+                // This is the only division, and is only done once per
+                // `Range`.
                 //
                 // The smallest integer prngs generate is u32. That is why for
                 // small integer sizes (i8/u8 and i16/u16) there is an
@@ -153,12 +337,10 @@ macro_rules! range_int_impl {
                 // This improves the chance to get a random integer that fits in
                 // the zone to 998 in 1000 in the worst case.
                 //
-                // There is a problem however: we can't store such a large range
+                // There is a problem however: we can't store such a large zone
                 // in `RangeInt`, that can only hold values of the size of $ty.
-                // `ints_to_reject` is always less than half the size of the
-                // small integer. For an u8 it only ever uses 7 bits. This means
-                // that all but the last 7 bits of `zone` are always 1's (or 15
-                // in the case of u16). So nothing is lost by trucating `zone`.
+                // But `ints_to_reject` is less than `range`, so it always fits
+                // in $unsigned; we store that and recompute `zone` from it.
 
                 let unsigned_max: $u_large = ::core::$u_large::MAX;
 
@@ -171,29 +353,26 @@ macro_rules! range_int_impl {
                     } else {
                         0
                     };
-                let zone = unsigned_max - ints_to_reject;
 
                 RangeInt {
                     low: low,
                     // These are really $unsigned values, but store as $ty:
                     range: range as $ty,
-                    zone: zone as $ty
+                    ints_to_reject: ints_to_reject as $ty
                 }
             }
 
             fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Self::X {
                 let range = self.range as $unsigned as $u_large;
                 if range > 0 {
-                    // Some casting to recover the trucated bits of `zone`:
-                    // First bit-cast to a signed int. Next sign-extend to the
-                    // larger type. Then bit-cast to unsigned.
-                    // For types that already have the right size, all the
-                    // casting is a no-op.
-                    let zone = self.zone as $signed as $i_large as $u_large;
+                    let unsigned_max: $u_large = ::core::$u_large::MAX;
+                    let ints_to_reject = self.ints_to_reject as $unsigned as $u_large;
+                    let zone = unsigned_max - ints_to_reject;
                     loop {
                         let v: $u_large = Rand::rand(rng, Uniform);
-                        if v <= zone {
-                            return self.low.wrapping_add((v % range) as $ty);
+                        let (hi, lo) = v.wmul(range);
+                        if lo <= zone {
+                            return self.low.wrapping_add(hi as $ty);
                         }
                     }
                 } else {
@@ -201,28 +380,168 @@ macro_rules! range_int_impl {
                     Rand::rand(rng, Uniform)
                 }
             }
+
+            fn sample_single<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R)
+                -> Self::X
+            {
+                Self::sample_single_inclusive(low, high - 1, rng)
+            }
+
+            fn sample_single_inclusive<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R)
+                -> Self::X
+            {
+                let range = (high as $u_large)
+                            .wrapping_sub(low as $u_large)
+                            .wrapping_add(1);
+                if range == 0 {
+                    // Sample from the entire integer range.
+                    return Rand::rand(rng, Uniform);
+                }
+                // Lemire's method in its original form: reject the products
+                // with a low word below `2^N % range`, rather than above a
+                // zone as in `sample`. That leaves the same number of
+                // accepted integers for every output, but only needs the
+                // division when the low word is below `range`, which is rare
+                // for the small ranges `gen_range` is typically used with.
+                let v: $u_large = Rand::rand(rng, Uniform);
+                let (mut hi, mut lo) = v.wmul(range);
+                if lo < range {
+                    // `range.wrapping_neg()` is `2^N - range`
+                    let ints_to_reject = range.wrapping_neg() % range;
+                    while lo < ints_to_reject {
+                        let v: $u_large = Rand::rand(rng, Uniform);
+                        let (new_hi, new_lo) = v.wmul(range);
+                        hi = new_hi;
+                        lo = new_lo;
+                    }
+                }
+                low.wrapping_add(hi as $ty)
+            }
+        }
+    }
+}
+
+range_int_impl! { i8, u8, u32 }
+range_int_impl! { i16, u16, u32 }
+range_int_impl! { i32, u32, u32 }
+range_int_impl! { i64, u64, u64 }
+#[cfg(feature = "i128_support")]
+range_int_impl! { i128, u128, u128 }
+range_int_impl! { isize, usize, usize }
+range_int_impl! { u8, u8, u32 }
+range_int_impl! { u16, u16, u32 }
+range_int_impl! { u32, u32, u32 }
+range_int_impl! { u64, u64, u64 }
+#[cfg(feature = "i128_support")]
+range_int_impl! { u128, u128, u128 }
+range_int_impl! { usize, usize, usize }
+
+/// Multiplication returning both the high and the low word of the full,
+/// double width product, as `(high, low)`.
+trait WideningMultiply<RHS = Self> {
+    type Output;
+
+    fn wmul(self, x: RHS) -> Self::Output;
+}
+
+macro_rules! wmul_impl {
+    ($ty:ty, $wide:ty, $shift:expr) => {
+        impl WideningMultiply for $ty {
+            type Output = ($ty, $ty);
+
+            #[inline(always)]
+            fn wmul(self, x: $ty) -> Self::Output {
+                let tmp = (self as $wide) * (x as $wide);
+                ((tmp >> $shift) as $ty, tmp as $ty)
+            }
         }
     }
 }
 
-range_int_impl! { i8, i8, u8, i32, u32 }
-range_int_impl! { i16, i16, u16, i32, u32 }
-range_int_impl! { i32, i32, u32, i32, u32 }
-range_int_impl! { i64, i64, u64, i64, u64 }
+wmul_impl! { u32, u64, 32 }
 #[cfg(feature = "i128_support")]
-range_int_impl! { i128, i128, u128, u128, u128 }
-range_int_impl! { isize, isize, usize, isize, usize }
-range_int_impl! { u8, i8, u8, i32, u32 }
-range_int_impl! { u16, i16, u16, i32, u32 }
-range_int_impl! { u32, i32, u32, i32, u32 }
-range_int_impl! { u64, i64, u64, i64, u64 }
+wmul_impl! { u64, u128, 64 }
+
+// Without a double width type, build the product from the four products of
+// the half words, as in schoolbook multiplication.
+macro_rules! wmul_impl_large {
+    ($ty:ty, $half:expr) => {
+        impl WideningMultiply for $ty {
+            type Output = ($ty, $ty);
+
+            #[inline(always)]
+            fn wmul(self, b: $ty) -> Self::Output {
+                const LOWER_MASK: $ty = !0 >> $half;
+                let mut low = (self & LOWER_MASK).wrapping_mul(b & LOWER_MASK);
+                let mut t = low >> $half;
+                low &= LOWER_MASK;
+                t += (self >> $half).wrapping_mul(b & LOWER_MASK);
+                low += (t & LOWER_MASK) << $half;
+                let mut high = t >> $half;
+                t = low >> $half;
+                low &= LOWER_MASK;
+                t += (b >> $half).wrapping_mul(self & LOWER_MASK);
+                low += (t & LOWER_MASK) << $half;
+                high += t >> $half;
+                high += (self >> $half).wrapping_mul(b >> $half);
+
+                (high, low)
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "i128_support"))]
+wmul_impl_large! { u64, 32 }
 #[cfg(feature = "i128_support")]
-range_int_impl! { u128, u128, u128, i128, u128 }
-range_int_impl! { usize, isize, usize, isize, usize }
+wmul_impl_large! { u128, 64 }
+
+macro_rules! wmul_impl_usize {
+    ($ty:ty) => {
+        impl WideningMultiply for usize {
+            type Output = (usize, usize);
+
+            #[inline(always)]
+            fn wmul(self, x: usize) -> Self::Output {
+                let (high, low) = (self as $ty).wmul(x as $ty);
+                (high as usize, low as usize)
+            }
+        }
+    }
+}
+
+#[cfg(target_pointer_width = "32")]
+wmul_impl_usize! { u32 }
+#[cfg(target_pointer_width = "64")]
+wmul_impl_usize! { u64 }
+
+/// Float types for which `Range` offers a high precision mode; see
+/// `Range::new_high_precision`. This is implemented for `f32` and `f64`.
+pub trait SampleRangeFloat: SampleRange {
+    #[doc(hidden)]
+    fn new_high_precision(low: Self, high: Self, inclusive: bool) -> Self::T;
+}
 
 /// Implementation of `RangeImpl` for float types.
+///
+/// Samples always lie within `[low, high)`, or `[low, high]` for an inclusive
+/// range, even after rounding (as long as both bounds are finite).
 #[derive(Clone, Copy, Debug)]
-pub enum RangeFloat<X> {
+pub struct RangeFloat<X> {
+    low: X,
+    high: X,
+    inclusive: bool,
+    repr: RangeFloatRepr<X>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum RangeFloatRepr<X> {
+    // `low + scale * x` for `x` in `[0, 1)` with fixed precision, with
+    // `scale` chosen such that the result never rounds outside the range
+    Fixed { scale: X },
+    // The high precision variants below may round outside the range, in
+    // which case the sample is rejected.
+    //
     // A range that is completely positive
     Positive { offset: X, scale: X },
     // A range that is completely negative
@@ -236,12 +555,12 @@ pub enum RangeFloat<X> {
 }
 
 macro_rules! range_float_impl {
-    ($ty:ty, $next_u:path) => {
+    ($ty:ident, $next_u:path) => {
         impl SampleRange for $ty {
             type T = RangeFloat<$ty>;
         }
 
-        impl RangeImpl for RangeFloat<$ty> {
+        impl SampleRangeFloat for $ty {
             // We can distinguish between two different kinds of ranges:
             //
             // Completely positive or negative.
@@ -268,51 +587,103 @@ macro_rules! range_float_impl {
             // Scaling keeps the bits of precision intact. Moving the assymetric
             // part also does not waste precision, as the more you move away
             // from zero the less precision can be stored.
-
-            type X = $ty;
-
-            fn new(low: Self::X, high: Self::X) -> Self {
-                if low >= 0.0 {
-                    RangeFloat::Positive {
+            //
+            // For an inclusive range the same is done: `high` itself has
+            // about zero probability either way, but may now be the result of
+            // rounding.
+            fn new_high_precision(low: $ty, high: $ty, inclusive: bool) -> RangeFloat<$ty> {
+                assert!(low.is_finite() && high.is_finite(),
+                        "high precision float range with an infinite bound");
+                let repr = if low >= 0.0 {
+                    RangeFloatRepr::Positive {
                         offset: low,
                         scale: high - low,
                     }
                 } else if high <= 0.0 {
-                    RangeFloat::Negative {
+                    RangeFloatRepr::Negative {
                         offset: high,
                         scale: low - high,
                     }
                 } else if -low <= high {
-                    RangeFloat::PosAndNeg {
+                    RangeFloatRepr::PosAndNeg {
                         cutoff: low,
                         scale: (high + low) / -2.0 + low,
                     }
                 } else {
-                    RangeFloat::NegAndPos {
+                    RangeFloatRepr::NegAndPos {
                         cutoff: high,
                         scale: (high + low) / 2.0 - high,
                     }
-                }
+                };
+                RangeFloat { low, high, inclusive, repr }
             }
+        }
 
-            fn new_inclusive(low: Self::X, high: Self::X) -> Self {
-                // Same as `new`, because the boundaries of a floats range are
-                // (at least currently) not exact due to rounding errors.
-                RangeImpl::new(low, high)
+        impl RangeFloat<$ty> {
+            fn new_fixed(low: $ty, high: $ty, inclusive: bool) -> Self {
+                // The largest value `closed_open01_fixed` returns.
+                let max_rand: $ty = 1.0 - ::core::$ty::EPSILON;
+                let mut scale = if inclusive {
+                    (high - low) / max_rand
+                } else {
+                    high - low
+                };
+                if !scale.is_finite() {
+                    if low.is_finite() && high.is_finite() {
+                        // `high - low` overflows, but the high precision
+                        // method can handle this range.
+                        return <$ty as SampleRangeFloat>::new_high_precision(
+                            low, high, inclusive);
+                    }
+                    // With an infinite bound there is nothing to adjust.
+                    return RangeFloat { low, high, inclusive,
+                                        repr: RangeFloatRepr::Fixed { scale } };
+                }
+                // Rounding is monotonic, so if the largest sample is within
+                // the range all samples are. Otherwise find the largest
+                // `scale` for which it is, by a binary search over the bits
+                // of `scale`: they are ordered like the values of positive
+                // floats, and a `scale` of zero always works. Decreasing
+                // `scale` one ULP at a time is not enough, as `high` may be
+                // much larger than `scale`.
+                let in_range = |scale: $ty| {
+                    let max = low + scale * max_rand;
+                    max < high || (inclusive && max == high)
+                };
+                if !in_range(scale) {
+                    let (mut good, mut bad) = (0, scale.to_bits());
+                    while bad - good > 1 {
+                        let mid = good + (bad - good) / 2;
+                        if in_range($ty::from_bits(mid)) {
+                            good = mid;
+                        } else {
+                            bad = mid;
+                        }
+                    }
+                    scale = $ty::from_bits(good);
+                }
+                RangeFloat { low, high, inclusive, repr: RangeFloatRepr::Fixed { scale } }
             }
 
-            fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Self::X {
+            /// Sample a value, which for the high precision method may have
+            /// been rounded outside the range.
+            #[inline]
+            fn sample_unchecked<R: Rng+?Sized>(&self, rng: &mut R) -> $ty {
                 let rnd = $next_u(rng);
-                match *self {
-                    RangeFloat::Positive { offset, scale } => {
+                match self.repr {
+                    RangeFloatRepr::Fixed { scale } => {
+                        let x: $ty = rnd.closed_open01_fixed();
+                        self.low + scale * x
+                    }
+                    RangeFloatRepr::Positive { offset, scale } => {
                         let x: $ty = rnd.closed_open01();
                         offset + scale * x
                     }
-                    RangeFloat::Negative { offset, scale } => {
+                    RangeFloatRepr::Negative { offset, scale } => {
                         let x: $ty = rnd.open_closed01();
                         offset + scale * x
                     }
-                    RangeFloat::PosAndNeg { cutoff, scale } => {
+                    RangeFloatRepr::PosAndNeg { cutoff, scale } => {
                         let x: $ty = rnd.closed_open11();
                         let x = x * scale;
                         if x < cutoff {
@@ -321,7 +692,7 @@ macro_rules! range_float_impl {
                             x
                         }
                     }
-                    RangeFloat::NegAndPos { cutoff, scale } => {
+                    RangeFloatRepr::NegAndPos { cutoff, scale } => {
                         let x: $ty = rnd.closed_open11();
                         let x = x * scale;
                         if x >= cutoff {
@@ -333,17 +704,308 @@ macro_rules! range_float_impl {
                 }
             }
         }
+
+        impl RangeImpl for RangeFloat<$ty> {
+            type X = $ty;
+
+            fn new(low: Self::X, high: Self::X) -> Self {
+                Self::new_fixed(low, high, false)
+            }
+
+            fn new_inclusive(low: Self::X, high: Self::X) -> Self {
+                Self::new_fixed(low, high, true)
+            }
+
+            fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Self::X {
+                loop {
+                    let x = self.sample_unchecked(rng);
+                    if let RangeFloatRepr::Fixed { .. } = self.repr {
+                        return x;
+                    }
+                    if x >= self.low && (x < self.high || (self.inclusive && x == self.high)) {
+                        return x;
+                    }
+                }
+            }
+        }
     }
 }
 
 range_float_impl! { f32, Rng::next_u32 }
 range_float_impl! { f64, Rng::next_u64 }
 
+/// Implementation of `RangeImpl` for `char`.
+///
+/// Samples are uniformly distributed over the Unicode scalar values in the
+/// range, i.e. the surrogate code points `0xD800...0xDFFF` are skipped, as
+/// they are by `codepoint`.
+#[derive(Clone, Copy, Debug)]
+pub struct RangeChar {
+    inner: RangeInt<u32>,
+}
+
+/// The first surrogate code point.
+const CHAR_SURROGATE_START: u32 = 0xD800;
+/// The number of surrogate code points.
+const CHAR_SURROGATE_LEN: u32 = 0xE000 - CHAR_SURROGATE_START;
+
+/// Map a `char` to a `u32` such that the scalar values are consecutive.
+fn char_to_comp_u32(c: char) -> u32 {
+    match c as u32 {
+        c if c >= CHAR_SURROGATE_START => c - CHAR_SURROGATE_LEN,
+        c => c,
+    }
+}
+
+/// The inverse of `char_to_comp_u32`.
+fn comp_u32_to_char(x: u32) -> char {
+    let x = if x >= CHAR_SURROGATE_START { x + CHAR_SURROGATE_LEN } else { x };
+    // `x` is a scalar value, as it lies between the values of two `char`s
+    // and skips the surrogates.
+    ::core::char::from_u32(x).unwrap()
+}
+
+impl SampleRange for char {
+    type T = RangeChar;
+}
+
+impl RangeImpl for RangeChar {
+    type X = char;
+
+    fn new(low: char, high: char) -> Self {
+        RangeChar {
+            inner: RangeImpl::new(char_to_comp_u32(low), char_to_comp_u32(high)),
+        }
+    }
+
+    fn new_inclusive(low: char, high: char) -> Self {
+        RangeChar {
+            inner: RangeImpl::new_inclusive(char_to_comp_u32(low), char_to_comp_u32(high)),
+        }
+    }
+
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> char {
+        comp_u32_to_char(self.inner.sample(rng))
+    }
+
+    fn sample_single_inclusive<R: Rng+?Sized>(low: char, high: char, rng: &mut R) -> char {
+        let x = RangeInt::<u32>::sample_single_inclusive(
+            char_to_comp_u32(low), char_to_comp_u32(high), rng);
+        comp_u32_to_char(x)
+    }
+
+    fn sample_single<R: Rng+?Sized>(low: char, high: char, rng: &mut R) -> char {
+        let x = RangeInt::<u32>::sample_single(
+            char_to_comp_u32(low), char_to_comp_u32(high), rng);
+        comp_u32_to_char(x)
+    }
+}
+
+/// Implementation of `RangeImpl` for `Wrapping<T>`, sampling like the range
+/// of the wrapped values.
+#[derive(Clone, Copy, Debug)]
+pub struct RangeWrapping<T> {
+    inner: T,
+}
+
+impl<X: SampleRange> SampleRange for Wrapping<X> {
+    type T = RangeWrapping<X::T>;
+}
+
+impl<T: RangeImpl> RangeImpl for RangeWrapping<T> {
+    type X = Wrapping<T::X>;
+
+    fn new(low: Self::X, high: Self::X) -> Self {
+        RangeWrapping { inner: T::new(low.0, high.0) }
+    }
+
+    fn new_inclusive(low: Self::X, high: Self::X) -> Self {
+        RangeWrapping { inner: T::new_inclusive(low.0, high.0) }
+    }
+
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Self::X {
+        Wrapping(self.inner.sample(rng))
+    }
+
+    fn sample_single<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R) -> Self::X {
+        Wrapping(T::sample_single(low.0, high.0, rng))
+    }
+
+    fn sample_single_inclusive<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R)
+        -> Self::X
+    {
+        Wrapping(T::sample_single_inclusive(low.0, high.0, rng))
+    }
+}
+
+/// Implementation of `RangeImpl` for the `NonZero*` integer types.
+///
+/// Samples are uniformly distributed over the non-zero values in the range;
+/// for the signed types the range may include zero, which is skipped.
+#[derive(Clone, Copy, Debug)]
+pub struct RangeNonZero<X> {
+    inner: RangeInt<X>,
+}
+
+macro_rules! range_non_zero_impl {
+    ($non_zero:ident, $ty:ident, $to_comp:expr, $from_comp:expr) => {
+        impl SampleRange for $non_zero {
+            type T = RangeNonZero<$ty>;
+        }
+
+        impl RangeNonZero<$ty> {
+            /// Map the non-zero values to consecutive integers.
+            #[inline]
+            fn to_comp(x: $non_zero) -> $ty {
+                let f: fn($ty) -> $ty = $to_comp;
+                f(x.get())
+            }
+
+            /// The inverse of `to_comp`.
+            #[inline]
+            fn from_comp(x: $ty) -> $non_zero {
+                let f: fn($ty) -> $ty = $from_comp;
+                $non_zero::new(f(x)).unwrap()
+            }
+        }
+
+        impl RangeImpl for RangeNonZero<$ty> {
+            type X = $non_zero;
+
+            fn new(low: Self::X, high: Self::X) -> Self {
+                RangeNonZero { inner: RangeImpl::new(Self::to_comp(low), Self::to_comp(high)) }
+            }
+
+            fn new_inclusive(low: Self::X, high: Self::X) -> Self {
+                RangeNonZero {
+                    inner: RangeImpl::new_inclusive(Self::to_comp(low), Self::to_comp(high)),
+                }
+            }
+
+            fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Self::X {
+                Self::from_comp(self.inner.sample(rng))
+            }
+
+            fn sample_single<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R)
+                -> Self::X
+            {
+                Self::from_comp(RangeInt::<$ty>::sample_single(
+                    Self::to_comp(low), Self::to_comp(high), rng))
+            }
+
+            fn sample_single_inclusive<R: Rng+?Sized>(low: Self::X, high: Self::X, rng: &mut R)
+                -> Self::X
+            {
+                Self::from_comp(RangeInt::<$ty>::sample_single_inclusive(
+                    Self::to_comp(low), Self::to_comp(high), rng))
+            }
+        }
+    };
+    // Unsigned values start at one, so shift them all down by one.
+    (unsigned $non_zero:ident, $ty:ident) => {
+        range_non_zero_impl! { $non_zero, $ty, |x| x - 1, |x| x + 1 }
+    };
+    // Signed values close the gap at zero by shifting the positive values
+    // down by one.
+    (signed $non_zero:ident, $ty:ident) => {
+        range_non_zero_impl! { $non_zero, $ty,
+                               |x| if x > 0 { x - 1 } else { x },
+                               |x| if x >= 0 { x + 1 } else { x } }
+    };
+}
+
+range_non_zero_impl! { unsigned NonZeroU8, u8 }
+range_non_zero_impl! { unsigned NonZeroU16, u16 }
+range_non_zero_impl! { unsigned NonZeroU32, u32 }
+range_non_zero_impl! { unsigned NonZeroU64, u64 }
+#[cfg(feature = "i128_support")]
+range_non_zero_impl! { unsigned NonZeroU128, u128 }
+range_non_zero_impl! { unsigned NonZeroUsize, usize }
+range_non_zero_impl! { signed NonZeroI8, i8 }
+range_non_zero_impl! { signed NonZeroI16, i16 }
+range_non_zero_impl! { signed NonZeroI32, i32 }
+range_non_zero_impl! { signed NonZeroI64, i64 }
+#[cfg(feature = "i128_support")]
+range_non_zero_impl! { signed NonZeroI128, i128 }
+range_non_zero_impl! { signed NonZeroIsize, isize }
+
+/// Implementation of `RangeImpl` for `Duration`.
+#[cfg(feature="std")]
+#[derive(Clone, Copy, Debug)]
+pub struct RangeDuration {
+    low: Duration,
+    repr: RangeDurationRepr,
+}
+
+#[cfg(feature="std")]
+#[derive(Clone, Copy, Debug)]
+enum RangeDurationRepr {
+    // The width of the range in nanoseconds fits in a `u64` (about 584
+    // years), so sample the offset from `low` in nanoseconds.
+    Nanos(RangeInt<u64>),
+    // Otherwise sample the seconds and nanoseconds of the offset separately,
+    // rejecting offsets past `max`.
+    Large { secs: RangeInt<u64>, max: Duration },
+}
+
+#[cfg(feature="std")]
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+#[cfg(feature="std")]
+impl SampleRange for Duration {
+    type T = RangeDuration;
+}
+
+#[cfg(feature="std")]
+impl RangeImpl for RangeDuration {
+    type X = Duration;
+
+    fn new(low: Duration, high: Duration) -> Self {
+        RangeImpl::new_inclusive(low, high - Duration::new(0, 1))
+    }
+
+    fn new_inclusive(low: Duration, high: Duration) -> Self {
+        let max = high - low;
+        let max_nanos = max.as_secs().checked_mul(NANOS_PER_SEC as u64)
+            .and_then(|nanos| nanos.checked_add(max.subsec_nanos() as u64));
+        let repr = match max_nanos {
+            Some(max_nanos) => RangeDurationRepr::Nanos(RangeImpl::new_inclusive(0, max_nanos)),
+            None => RangeDurationRepr::Large {
+                secs: RangeImpl::new_inclusive(0, max.as_secs()),
+                max,
+            },
+        };
+        RangeDuration { low, repr }
+    }
+
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Duration {
+        let offset = match self.repr {
+            RangeDurationRepr::Nanos(ref nanos) => {
+                let nanos = nanos.sample(rng);
+                Duration::new(nanos / NANOS_PER_SEC as u64, (nanos % NANOS_PER_SEC as u64) as u32)
+            }
+            RangeDurationRepr::Large { ref secs, max } => {
+                // The seconds of `max` are at least 18 billion here, so
+                // rejection is very rare.
+                let subsec_nanos: RangeInt<u32> = RangeImpl::new(0, NANOS_PER_SEC);
+                loop {
+                    let offset = Duration::new(secs.sample(rng), subsec_nanos.sample(rng));
+                    if offset <= max {
+                        break offset;
+                    }
+                }
+            }
+        };
+        self.low + offset
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {Rng, thread_rng};
     use distributions::{Rand, Distribution};
-    use distributions::range::{Range, RangeImpl, RangeFloat, SampleRange};
+    use std::f64;
+    use distributions::range::{Range, RangeError, RangeImpl, RangeFloat, SampleRange};
 
     #[test]
     fn test_fn_range() {
@@ -388,6 +1050,22 @@ mod tests {
         Range::new(10, 5);
     }
 
+    #[test]
+    fn test_range_try_new() {
+        assert_eq!(Range::try_new(10, 10).unwrap_err(), RangeError::EmptyRange);
+        assert_eq!(Range::try_new(1.0, 0.5).unwrap_err(), RangeError::EmptyRange);
+        assert_eq!(Range::try_new(0.0, f64::NAN).unwrap_err(), RangeError::NanBound);
+        assert_eq!(Range::try_new_inclusive(f64::NAN, 1.0).unwrap_err(),
+                   RangeError::NanBound);
+        assert_eq!(Range::try_new_inclusive(10, 9).unwrap_err(), RangeError::EmptyRange);
+
+        let mut rng = ::test::rng();
+        assert_eq!(Range::try_new_inclusive(10, 10).unwrap().sample(&mut rng), 10);
+        assert_eq!(Range::try_new_inclusive(-2.5, -2.5).unwrap().sample(&mut rng), -2.5);
+        let x = Range::try_new(0u8, 255).unwrap().sample(&mut rng);
+        assert!(x < 255);
+    }
+
     #[test]
     fn test_integers() {
         let mut rng = ::test::rng();
@@ -402,6 +1080,8 @@ mod tests {
                         for _ in 0..1000 {
                             let v: $ty = Rand::rand(&mut rng, my_range);
                             assert!(low <= v && v < high);
+                            let v: $ty = Range::sample_single(low, high, &mut rng);
+                            assert!(low <= v && v < high);
                         }
                     }
                  )*
@@ -413,6 +1093,137 @@ mod tests {
         t!(i128, u128)
     }
 
+    #[test]
+    fn test_wmul() {
+        use super::WideningMultiply;
+
+        assert_eq!(0xffff_ffffu32.wmul(0xffff_ffff), (0xffff_fffe, 1));
+        assert_eq!(0x1234_5678u32.wmul(0x10), (0x1, 0x2345_6780));
+        assert_eq!(0xffff_ffff_ffff_ffffu64.wmul(0xffff_ffff_ffff_ffff),
+                   (0xffff_ffff_ffff_fffe, 1));
+        assert_eq!(0x8000_0000_0000_0000u64.wmul(6), (3, 0));
+        assert_eq!(0x0123_4567_89ab_cdefu64.wmul(0xfedc_ba98_7654_3210),
+                   (0x0121_fa00_ad77_d742, 0x2236_d88f_e561_8cf0));
+        assert_eq!(7usize.wmul(3), (0, 21));
+        #[cfg(feature = "i128_support")]
+        {
+            assert_eq!((!0u128).wmul(!0), (!0 - 1, 1));
+            assert_eq!((1u128 << 127).wmul(6), (3, 0));
+        }
+    }
+
+    #[test]
+    fn test_integers_uniform() {
+        // Neither range divides the number of possible random integers, so
+        // some products have to be rejected.
+        use SeedableRng;
+        use distributions::test::assert_discrete_fit;
+        use prng::XorShiftRng;
+
+        let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+        let range = Range::new(0u8, 171);
+        let samples: Vec<u64> = (0..50_000).map(|_| range.sample(&mut rng) as u64).collect();
+        assert_discrete_fit(&samples, |k| if k < 171 { 1. / 171. } else { 0. });
+        // `2^32 % range` is above `u16::MAX / 2` here
+        let range = Range::new(0u16, 32971);
+        for _ in 0..1000 {
+            assert!(range.sample(&mut rng) < 32971);
+        }
+
+        let range = Range::new(0u64, 3 << 62);
+        let counts = (0..30_000).fold([0u64; 3], |mut counts, _| {
+            counts[(range.sample(&mut rng) >> 62) as usize] += 1;
+            counts
+        });
+        for &count in counts.iter() {
+            // the standard deviation is about 82
+            assert!((count as f64 - 10_000.).abs() < 500., "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_sample_single_uniform() {
+        use SeedableRng;
+        use distributions::test::assert_discrete_fit;
+        use prng::XorShiftRng;
+
+        let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+        let samples: Vec<u64> = (0..50_000)
+            .map(|_| Range::sample_single(0u8, 171, &mut rng) as u64).collect();
+        assert_discrete_fit(&samples, |k| if k < 171 { 1. / 171. } else { 0. });
+
+        // almost half of the products are rejected for this range
+        let samples: Vec<u64> = (0..30_000)
+            .map(|_| (Range::sample_single(0u32, 0x8000_0003, &mut rng) >> 29) as u64)
+            .collect();
+        // the topmost of the 5 groups only holds the 3 values from 2^31
+        assert_discrete_fit(&samples, |k| if k < 4 { 0.25 } else { 0. });
+
+        let samples: Vec<i64> = (0..1000)
+            .map(|_| Range::sample_single(-3i64, 3, &mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= -3 && x < 3));
+        for x in -3..3 {
+            assert!(samples.contains(&x));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_single_empty() {
+        Range::sample_single(3, 3, &mut ::test::rng());
+    }
+
+    #[test]
+    fn test_range_syntax() {
+        use std::{u8, u64, i64};
+        use Sample;
+
+        let mut rng = ::test::rng();
+        let mut seen = [false; 256];
+        for _ in 0..10_000 {
+            seen[rng.gen_range(0..=u8::MAX) as usize] = true;
+            seen[Range::from(0..=u8::MAX).sample(&mut rng) as usize] = true;
+        }
+        assert!(seen.iter().all(|&x| x));
+
+        for _ in 0..1000 {
+            let _: u64 = rng.gen_range(0..=u64::MAX);
+            let x = rng.gen_range(i64::MIN..=i64::MIN + 2);
+            assert!(x <= i64::MIN + 2);
+            assert_eq!(rng.gen_range(-7..=-7), -7);
+            assert_eq!(rng.gen_range(-7..-6), -7);
+            let x = Range::from(10u16..12).sample(&mut rng);
+            assert!(x >= 10 && x < 12);
+            let x = rng.gen_range(-1.5f32..=2.5);
+            assert!(x >= -1.5 && x <= 2.5);
+            let x = Range::from(0.5..1.0).sample(&mut rng);
+            assert!(x >= 0.5 && x < 1.0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_empty() {
+        use Sample;
+        let (low, high) = (3, 3);
+        ::test::rng().gen_range(low..high);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_empty_inclusive() {
+        use Sample;
+        let (low, high) = (3, 2);
+        ::test::rng().gen_range(low..=high);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_from_empty() {
+        let (low, high) = (1.0, 1.0);
+        let _ = Range::from(low..high);
+    }
+
     #[test]
     fn test_floats() {
         let mut rng = ::test::rng();
@@ -428,6 +1239,8 @@ mod tests {
                         for _ in 0..1000 {
                             let v: $ty = Rand::rand(&mut rng, my_range);
                             assert!(low <= v && v < high);
+                            let v: $ty = Range::sample_single(low, high, &mut rng);
+                            assert!(low <= v && v < high);
                         }
                     }
                  )*
@@ -437,6 +1250,237 @@ mod tests {
         t!(f32, f64)
     }
 
+    #[test]
+    fn test_floats_adversarial() {
+        use SeedableRng;
+        use Sample;
+        use mock::ConstRng;
+        use prng::XorShiftRng;
+
+        let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+        // Random words that make the fixed precision conversion return its
+        // smallest and largest values, and some with many zero bits.
+        let words = [0, !0, 1, 1 << 63, 0xffff_ffff, 0xffff_ffff_0000_0000];
+        macro_rules! t {
+            ($ty:ident) => {{
+                let next_up = |x: $ty| if x >= 0.0 {
+                    $ty::from_bits((x + 0.0).to_bits() + 1)
+                } else {
+                    $ty::from_bits(x.to_bits() - 1)
+                };
+                let next_down = |x: $ty| -next_up(-x);
+                let tiny = $ty::from_bits(1);
+                let big = ::std::$ty::MAX;
+                let bounds: &[($ty, $ty)] = &[
+                    // subnormals
+                    (0.0, tiny), (tiny, 5.0 * tiny), (-tiny, tiny), (-5.0 * tiny, -0.0),
+                    (::std::$ty::MIN_POSITIVE - tiny, ::std::$ty::MIN_POSITIVE),
+                    // one ULP apart
+                    (1.0, next_up(1.0)), (next_down(1.0), 1.0), (-1.0, next_up(-1.0)),
+                    (1e10, next_up(1e10)), (next_down(-1e10), -1e10),
+                    (next_down(big), big), (-big, next_up(-big)),
+                    // crossing zero
+                    (-1.0, 1.0), (-1e-30, 1e30), (-1e30, 1e-30), (-3.0, next_up(0.0)),
+                    // a width that overflows
+                    (-big, big), (-big, 1.0), (-1.0, big),
+                    // others
+                    (0.1, 0.3), (-7.0, -3.0), (1.0, 1.0 + 1e-6), (0.0, big),
+                ];
+                for &(low, high) in bounds.iter() {
+                    let ranges = [(Range::new(low, high), false),
+                                  (Range::new_inclusive(low, high), true),
+                                  (Range::new_high_precision(low, high), false),
+                                  (Range::new_inclusive_high_precision(low, high), true)];
+                    for &(range, inclusive) in ranges.iter() {
+                        let check = |x: $ty| {
+                            assert!(low <= x && (x < high || inclusive && x == high),
+                                    "{} sampled from {}..{} (inclusive: {})",
+                                    x, low, high, inclusive);
+                        };
+                        for _ in 0..1000 {
+                            check(range.sample(&mut rng));
+                        }
+                        // A constant generator would never leave the
+                        // rejection loop of the high precision method.
+                        if let super::RangeFloatRepr::Fixed { .. } = range.inner.repr {
+                            for &word in words.iter() {
+                                check(range.sample(&mut ConstRng::new(word)));
+                            }
+                        }
+                    }
+                    for _ in 0..100 {
+                        let x = rng.gen_range(low..high);
+                        assert!(low <= x && x < high);
+                        let x = rng.gen_range(low..=high);
+                        assert!(low <= x && x <= high);
+                    }
+                }
+
+                // a range of a single value
+                assert_eq!(Range::new_inclusive(-tiny, -tiny).sample(&mut rng), -tiny);
+                assert_eq!(Range::new_inclusive_high_precision(1e30, 1e30).sample(&mut rng),
+                           1e30);
+                // the largest fixed precision sample reaches `high` here
+                let range = Range::new_inclusive(0.0, 1.0);
+                assert_eq!(range.sample(&mut ConstRng::new(!0)), 1.0);
+            }}
+        }
+        t!(f32);
+        t!(f64);
+    }
+
+    #[test]
+    fn test_floats_high_precision() {
+        use mock::ConstRng;
+
+        // With the fixed precision a word of 1 is rounded to zero, while the
+        // high precision conversion has precision down to 2^-64 for f64.
+        let mut rng = ConstRng::new(1);
+        assert_eq!(Range::new(0.0, 1.0).sample(&mut rng), 0.0);
+        let x = Range::new_high_precision(0.0, 1.0).sample(&mut rng);
+        assert_eq!(x, 1.0 / 18446744073709551616.0);
+        let x = Range::new_high_precision(0.0f32, 1.0).sample(&mut rng);
+        assert_eq!(x, 1.0 / 4294967296.0);
+
+        let mut rng = ::test::rng();
+        for _ in 0..1000 {
+            let x = Range::new_high_precision(-3.0, 1e-3).sample(&mut rng);
+            assert!(x >= -3.0 && x < 1e-3);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_floats_high_precision_infinite() {
+        Range::new_high_precision(0.0, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_char() {
+        use Sample;
+
+        let mut rng = ::test::rng();
+        let range = Range::new('c', 'x');
+        for _ in 0..1000 {
+            let c = range.sample(&mut rng);
+            assert!(c >= 'c' && c < 'x');
+            let c = rng.gen_range('c'..='x');
+            assert!(c >= 'c' && c <= 'x');
+        }
+        assert_eq!(rng.gen_range('x'..'y'), 'x');
+        assert_eq!(Range::new_inclusive('y', 'y').sample(&mut rng), 'y');
+
+        // The surrogates are skipped, with both sides of the gap as likely.
+        let (low, high) = ('\u{D7FE}', '\u{E001}');
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let c = rng.gen_range(low..=high);
+            match c {
+                '\u{D7FE}' => counts[0] += 1,
+                '\u{D7FF}' => counts[1] += 1,
+                '\u{E000}' => counts[2] += 1,
+                '\u{E001}' => counts[3] += 1,
+                _ => panic!("{:?} out of range", c),
+            }
+        }
+        assert!(counts.iter().all(|&n| n > 850 && n < 1150), "{:?}", counts);
+
+        let range = Range::new_inclusive('\0', ::std::char::MAX);
+        for _ in 0..1000 {
+            range.sample(&mut rng);
+        }
+    }
+
+    #[test]
+    fn test_duration() {
+        use std::time::Duration;
+        use std::u64;
+        use Sample;
+
+        let mut rng = ::test::rng();
+        let bounds = [
+            (Duration::new(0, 1), Duration::new(0, 3)),
+            (Duration::new(1, 999_999_999), Duration::new(2, 1)),
+            (Duration::from_millis(100), Duration::from_secs(30)),
+            (Duration::new(0, 0), Duration::new(u64::MAX, 999_999_999)),
+            (Duration::new(u64::MAX / 2, 7), Duration::new(u64::MAX, 3)),
+        ];
+        for &(low, high) in bounds.iter() {
+            let range = Range::new(low, high);
+            let range_inclusive = Range::new_inclusive(low, high);
+            for _ in 0..1000 {
+                let x = range.sample(&mut rng);
+                assert!(x >= low && x < high);
+                let x = range_inclusive.sample(&mut rng);
+                assert!(x >= low && x <= high);
+                let x = rng.gen_range(low..high);
+                assert!(x >= low && x < high);
+            }
+        }
+
+        // the offset from `low` is not just whole seconds or nanoseconds
+        let low = Duration::new(5, 0);
+        let range = Range::new(low, Duration::new(10, 0));
+        assert!((0..100).any(|_| range.sample(&mut rng).subsec_nanos() != 0));
+        assert_eq!(Range::new_inclusive(low, low).sample(&mut rng), low);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        use std::num::Wrapping;
+        use Sample;
+
+        let mut rng = ::test::rng();
+        let range = Range::new(Wrapping(-5i32), Wrapping(5));
+        for _ in 0..1000 {
+            let x = range.sample(&mut rng);
+            assert!(x >= Wrapping(-5) && x < Wrapping(5));
+            let x = rng.gen_range(Wrapping(250u8)..=Wrapping(255));
+            assert!(x >= Wrapping(250));
+            let x = rng.gen_range(Wrapping(1.5f64)..Wrapping(2.0));
+            assert!(x >= Wrapping(1.5) && x < Wrapping(2.0));
+        }
+    }
+
+    #[test]
+    fn test_non_zero() {
+        use std::num::{NonZeroI8, NonZeroU32, NonZeroI64, NonZeroUsize};
+        use Sample;
+
+        let mut rng = ::test::rng();
+        let (low, high) = (NonZeroU32::new(1).unwrap(), NonZeroU32::new(3).unwrap());
+        let range = Range::new(low, high);
+        for _ in 0..1000 {
+            let x = range.sample(&mut rng);
+            assert!(x >= low && x < high);
+        }
+        let x = rng.gen_range(NonZeroUsize::new(7).unwrap()..NonZeroUsize::new(8).unwrap());
+        assert_eq!(x.get(), 7);
+
+        // Zero is skipped, with all other values as likely.
+        let (low, high) = (NonZeroI8::new(-2).unwrap(), NonZeroI8::new(2).unwrap());
+        let mut counts = [0; 5];
+        for _ in 0..4000 {
+            counts[(rng.gen_range(low..=high).get() + 2) as usize] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        assert!(counts.iter().enumerate().all(|(i, &n)| i == 2 || (n > 850 && n < 1150)),
+                "{:?}", counts);
+
+        // the full range of the type
+        let (low, high) = (NonZeroI8::new(-128).unwrap(), NonZeroI8::new(127).unwrap());
+        let range = Range::new_inclusive(low, high);
+        let range_exclusive = Range::new(low, high);
+        for _ in 0..1000 {
+            range.sample(&mut rng);
+            assert!(range_exclusive.sample(&mut rng) < high);
+        }
+        let (low, high) = (NonZeroI64::new(-1).unwrap(), NonZeroI64::new(1).unwrap());
+        for _ in 0..100 {
+            assert_eq!(rng.gen_range(low..high), low);
+        }
+    }
+
     #[test]
     fn test_custom_range() {
         #[derive(Clone, Copy, PartialEq, PartialOrd)]
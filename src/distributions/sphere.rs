@@ -0,0 +1,233 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uniform distributions on the unit sphere, unit circle, unit ball and unit
+//! disc.
+
+use Rng;
+use distributions::{Distribution, Range};
+
+/// Samples uniformly from the surface of the unit sphere in three dimensions.
+///
+/// Implemented via a method by Marsaglia[1].
+///
+/// [1]: George Marsaglia (1972). [*Choosing a Point from the Surface of a
+/// Sphere*](https://doi.org/10.1214/aoms/1177692644). Ann. Math. Statist. 43,
+/// 2, 645-646.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{UnitSphereSurface, Distribution};
+///
+/// let v = UnitSphereSurface.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit sphere surface.", v)
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UnitSphereSurface;
+
+impl Distribution<[f64; 3]> for UnitSphereSurface {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> [f64; 3] {
+        let range = Range::new(-1f64, 1.);
+        loop {
+            let (x1, x2) = (range.sample(rng), range.sample(rng));
+            let sum = x1 * x1 + x2 * x2;
+            if sum >= 1. {
+                continue;
+            }
+            let factor = 2. * (1.0 - sum).sqrt();
+            return [x1 * factor, x2 * factor, 1. - 2. * sum];
+        }
+    }
+}
+
+/// Samples uniformly from the unit circle (the edge of the unit disc) in two
+/// dimensions.
+///
+/// Implemented via a method by von Neumann[1], which avoids trigonometric
+/// functions.
+///
+/// [1]: John von Neumann (1951). *Various Techniques Used in Connection with
+/// Random Digits*. National Bureau of Standards, Applied Mathematics Series
+/// 12, 36-38.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{UnitCircle, Distribution};
+///
+/// let v = UnitCircle.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit circle.", v)
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UnitCircle;
+
+impl Distribution<[f64; 2]> for UnitCircle {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        let range = Range::new(-1f64, 1.);
+        loop {
+            let (x1, x2) = (range.sample(rng), range.sample(rng));
+            let x1_sq = x1 * x1;
+            let x2_sq = x2 * x2;
+            let sum = x1_sq + x2_sq;
+            // also reject the origin, where the angle is undefined
+            if sum >= 1. || sum == 0. {
+                continue;
+            }
+            // (x1 + i x2)^2 / |x1 + i x2|^2 doubles the (uniform) angle
+            return [(x1_sq - x2_sq) / sum, 2. * x1 * x2 / sum];
+        }
+    }
+}
+
+/// Samples uniformly from the unit ball (the interior of the unit sphere) in
+/// three dimensions.
+///
+/// Implemented by rejection sampling from the enclosing cube.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{UnitBall, Distribution};
+///
+/// let v = UnitBall.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit ball.", v)
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UnitBall;
+
+impl Distribution<[f64; 3]> for UnitBall {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> [f64; 3] {
+        let range = Range::new(-1f64, 1.);
+        loop {
+            let x = [range.sample(rng), range.sample(rng), range.sample(rng)];
+            if x[0] * x[0] + x[1] * x[1] + x[2] * x[2] < 1. {
+                return x;
+            }
+        }
+    }
+}
+
+/// Samples uniformly from the unit disc (the interior of the unit circle) in
+/// two dimensions.
+///
+/// Implemented by rejection sampling from the enclosing square.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{UnitDisc, Distribution};
+///
+/// let v = UnitDisc.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit disc.", v)
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UnitDisc;
+
+impl Distribution<[f64; 2]> for UnitDisc {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        let range = Range::new(-1f64, 1.);
+        loop {
+            let x = [range.sample(rng), range.sample(rng)];
+            if x[0] * x[0] + x[1] * x[1] < 1. {
+                return x;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+    use SeedableRng;
+    use distributions::Distribution;
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{UnitSphereSurface, UnitCircle, UnitBall, UnitDisc};
+
+    const N: usize = 20_000;
+    const BINS: u64 = 24;
+
+    fn rng() -> XorShiftRng {
+        XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb])
+    }
+
+    /// The histogram bin of `x`, uniform on `[low, high]` if the
+    /// distribution is correct.
+    fn bin(x: f64, low: f64, high: f64) -> u64 {
+        (((x - low) / (high - low) * BINS as f64) as u64).min(BINS - 1)
+    }
+
+    /// Assert that the bins of some quantity are uniformly distributed.
+    fn assert_uniform(bins: &[u64]) {
+        assert_discrete_fit(bins, |k| if k < BINS { 1. / BINS as f64 } else { 0. });
+    }
+
+    #[test]
+    fn test_unit_sphere_surface() {
+        let mut rng = rng();
+        let samples: Vec<[f64; 3]> = (0..N).map(|_| UnitSphereSurface.sample(&mut rng)).collect();
+        for x in samples.iter() {
+            assert!((x[0] * x[0] + x[1] * x[1] + x[2] * x[2] - 1.).abs() < 1e-12);
+        }
+        // by Archimedes' hat-box theorem, each coordinate is uniform on
+        // [-1, 1]; the azimuth about each axis is uniform too
+        for axis in 0..3 {
+            let (a, b) = ((axis + 1) % 3, (axis + 2) % 3);
+            let heights: Vec<u64> = samples.iter().map(|x| bin(x[axis], -1., 1.)).collect();
+            assert_uniform(&heights);
+            let angles: Vec<u64> = samples.iter()
+                .map(|x| bin(x[b].atan2(x[a]), -PI, PI)).collect();
+            assert_uniform(&angles);
+        }
+    }
+
+    #[test]
+    fn test_unit_circle() {
+        let mut rng = rng();
+        let samples: Vec<[f64; 2]> = (0..N).map(|_| UnitCircle.sample(&mut rng)).collect();
+        for x in samples.iter() {
+            assert!((x[0] * x[0] + x[1] * x[1] - 1.).abs() < 1e-12);
+        }
+        let angles: Vec<u64> = samples.iter().map(|x| bin(x[1].atan2(x[0]), -PI, PI)).collect();
+        assert_uniform(&angles);
+    }
+
+    #[test]
+    fn test_unit_ball() {
+        let mut rng = rng();
+        let samples: Vec<[f64; 3]> = (0..N).map(|_| UnitBall.sample(&mut rng)).collect();
+        // the cube of the radius is uniform on [0, 1], and the direction is
+        // uniform on the sphere
+        let radii: Vec<f64> = samples.iter()
+            .map(|x| (x[0] * x[0] + x[1] * x[1] + x[2] * x[2]).sqrt()).collect();
+        assert!(radii.iter().all(|&r| r < 1.));
+        let volumes: Vec<u64> = radii.iter().map(|&r| bin(r * r * r, 0., 1.)).collect();
+        assert_uniform(&volumes);
+        let heights: Vec<u64> = samples.iter().zip(radii.iter())
+            .map(|(x, &r)| bin(x[2] / r, -1., 1.)).collect();
+        assert_uniform(&heights);
+        let angles: Vec<u64> = samples.iter().map(|x| bin(x[1].atan2(x[0]), -PI, PI)).collect();
+        assert_uniform(&angles);
+    }
+
+    #[test]
+    fn test_unit_disc() {
+        let mut rng = rng();
+        let samples: Vec<[f64; 2]> = (0..N).map(|_| UnitDisc.sample(&mut rng)).collect();
+        // the square of the radius is uniform on [0, 1], and the angle is
+        // uniform
+        let areas: Vec<u64> = samples.iter().map(|x| x[0] * x[0] + x[1] * x[1])
+            .inspect(|&r2| assert!(r2 < 1.)).map(|r2| bin(r2, 0., 1.)).collect();
+        assert_uniform(&areas);
+        let angles: Vec<u64> = samples.iter().map(|x| bin(x[1].atan2(x[0]), -PI, PI)).collect();
+        assert_uniform(&angles);
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The triangular distribution.
+
+use Rng;
+use distributions::{Distribution, Open01, Rand};
+
+/// The triangular distribution `Triangular(min, max, mode)`.
+///
+/// This distribution has a density which increases linearly from zero at
+/// `min` to its peak at `mode`, then decreases linearly to zero at `max`.
+///
+/// Samples are generated by inversion of the CDF.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Triangular, Distribution};
+///
+/// let tri = Triangular::new(0.0, 5.0, 2.5);
+/// let v = tri.sample(&mut rand::thread_rng());
+/// println!("{} is from a triangular distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Triangular {
+    min: f64,
+    max: f64,
+    mode: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Triangular` distribution.
+    pub enum TriangularError {
+        /// `min`, `max` or `mode` is NaN or infinite.
+        NotFinite => "a parameter is NaN or infinite",
+        /// `min >= max`.
+        EmptyRange => "`min` is not less than `max`",
+        /// `mode` is outside `[min, max]`.
+        ModeOutOfRange => "`mode` is outside [`min`, `max`]",
+    }
+}
+
+impl Triangular {
+    /// Construct a new `Triangular` with the given lower limit `min`, upper
+    /// limit `max` and `mode`.
+    ///
+    /// Panics unless all parameters are finite, `min < max` and
+    /// `min <= mode <= max`; see `try_new`.
+    pub fn new(min: f64, max: f64, mode: f64) -> Triangular {
+        Triangular::try_new(min, max, mode)
+            .unwrap_or_else(|e| panic!("Triangular::new: {}", e))
+    }
+
+    /// Construct a new `Triangular`, or return an error if the parameters
+    /// are invalid: `NotFinite` if any of them is NaN or infinite,
+    /// `EmptyRange` if `min >= max`, and `ModeOutOfRange` unless
+    /// `min <= mode <= max`.
+    pub fn try_new(min: f64, max: f64, mode: f64) -> Result<Triangular, TriangularError> {
+        if !(min.is_finite() && max.is_finite() && mode.is_finite()) {
+            return Err(TriangularError::NotFinite);
+        } else if min >= max {
+            return Err(TriangularError::EmptyRange);
+        } else if mode < min || mode > max {
+            return Err(TriangularError::ModeOutOfRange);
+        }
+        Ok(Triangular { min, max, mode })
+    }
+}
+
+impl Distribution<f64> for Triangular {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+        let u = f64::rand(rng, Open01);
+        let diff_mode = self.mode - self.min;
+        let diff_range = self.max - self.min;
+        // the CDF at the mode
+        let f_mode = diff_mode / diff_range;
+        if u <= f_mode {
+            self.min + (u * diff_range * diff_mode).sqrt()
+        } else {
+            self.max - ((1. - u) * diff_range * (self.max - self.mode)).sqrt()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use std::f64;
+    use distributions::test::assert_quantiles;
+    use super::{Triangular, TriangularError};
+
+    fn quantile(min: f64, max: f64, mode: f64, p: f64) -> f64 {
+        if p <= (mode - min) / (max - min) {
+            min + (p * (max - min) * (mode - min)).sqrt()
+        } else {
+            max - ((1. - p) * (max - min) * (max - mode)).sqrt()
+        }
+    }
+
+    #[test]
+    fn test_triangular_quantiles() {
+        for &(min, max, mode) in [(0.0, 1.0, 0.5), (-1.0, 4.0, 0.0),
+                                  (0.0, 1.0, 0.0), (0.0, 1.0, 1.0)].iter() {
+            assert_quantiles(Triangular::new(min, max, mode),
+                             |p| quantile(min, max, mode, p));
+        }
+    }
+
+    #[test]
+    fn test_triangular_bounds() {
+        let tri = Triangular::new(-3.0, 7.0, 6.0);
+        let mut rng = ::test::rng();
+        for _ in 0..1000 {
+            let x = tri.sample(&mut rng);
+            assert!(x >= -3.0 && x <= 7.0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_triangular_invalid_range() {
+        Triangular::new(1.0, 1.0, 1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_triangular_invalid_mode() {
+        Triangular::new(0.0, 1.0, 2.0);
+    }
+    #[test]
+    fn test_triangular_try_new() {
+        assert!(Triangular::try_new(0.0, 1.0, 1.0).is_ok());
+        assert_eq!(Triangular::try_new(0.0, f64::NAN, 0.5).unwrap_err(),
+                   TriangularError::NotFinite);
+        assert_eq!(Triangular::try_new(f64::NEG_INFINITY, 1.0, 0.5).unwrap_err(),
+                   TriangularError::NotFinite);
+        assert_eq!(Triangular::try_new(2.0, 1.0, 1.5).unwrap_err(),
+                   TriangularError::EmptyRange);
+        assert_eq!(Triangular::try_new(0.0, 1.0, -0.5).unwrap_err(),
+                   TriangularError::ModeOutOfRange);
+    }
+}
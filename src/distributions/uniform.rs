@@ -12,8 +12,9 @@
 
 use core::char;
 use core::mem;
+use core::num::Wrapping;
 
-use Rng;
+use {Rng, AsByteSliceMut};
 use distributions::{Distribution, Rand};
 use utils::FloatConversions;
 
@@ -82,13 +83,29 @@ pub struct Open01;
 pub struct Closed01;
 
 /// Sample values uniformly from the ASCII ranges z-a, A-Z, and 0-9.
+///
+/// `Alphanumeric` samples the same characters, and is faster.
 #[derive(Debug)]
 pub struct AsciiWordChar;
 
 
 // ----- actual implementations -----
 
+// Fill a slice of integers with a single call to `fill_bytes`. The bytes are
+// interpreted in little-endian order, so the result does not depend on the
+// endianness of the platform.
+macro_rules! fill_via_bytes {
+    ($ty:ty) => {
+        fn fill<R: Rng+?Sized>(&self, rng: &mut R, dest: &mut [$ty]) {
+            rng.fill_bytes(dest.as_byte_slice_mut());
+            dest.to_le();
+        }
+    }
+}
+
 impl Distribution<isize> for Uniform {
+    fill_via_bytes!(isize);
+
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> isize {
         if mem::size_of::<isize>() == 4 {
             i32::rand(rng, Uniform) as isize
@@ -99,6 +116,8 @@ impl Distribution<isize> for Uniform {
 }
 
 impl Distribution<i8> for Uniform {
+    fill_via_bytes!(i8);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> i8 {
         rng.next_u32() as i8
@@ -106,6 +125,8 @@ impl Distribution<i8> for Uniform {
 }
 
 impl Distribution<i16> for Uniform {
+    fill_via_bytes!(i16);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> i16 {
         rng.next_u32() as i16
@@ -113,6 +134,8 @@ impl Distribution<i16> for Uniform {
 }
 
 impl Distribution<i32> for Uniform {
+    fill_via_bytes!(i32);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> i32 {
         rng.next_u32() as i32
@@ -120,6 +143,8 @@ impl Distribution<i32> for Uniform {
 }
 
 impl Distribution<i64> for Uniform {
+    fill_via_bytes!(i64);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> i64 {
         rng.next_u64() as i64
@@ -128,6 +153,8 @@ impl Distribution<i64> for Uniform {
 
 #[cfg(feature = "i128_support")]
 impl Distribution<i128> for Uniform {
+    fill_via_bytes!(i128);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> i128 {
         rng.next_u128() as i128
@@ -135,6 +162,8 @@ impl Distribution<i128> for Uniform {
 }
 
 impl Distribution<usize> for Uniform {
+    fill_via_bytes!(usize);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> usize {
         if mem::size_of::<usize>() == 4 {
@@ -146,6 +175,8 @@ impl Distribution<usize> for Uniform {
 }
 
 impl Distribution<u8> for Uniform {
+    fill_via_bytes!(u8);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u8 {
         rng.next_u32() as u8
@@ -153,6 +184,8 @@ impl Distribution<u8> for Uniform {
 }
 
 impl Distribution<u16> for Uniform {
+    fill_via_bytes!(u16);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u16 {
         rng.next_u32() as u16
@@ -160,6 +193,8 @@ impl Distribution<u16> for Uniform {
 }
 
 impl Distribution<u32> for Uniform {
+    fill_via_bytes!(u32);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u32 {
         rng.next_u32()
@@ -167,6 +202,8 @@ impl Distribution<u32> for Uniform {
 }
 
 impl Distribution<u64> for Uniform {
+    fill_via_bytes!(u64);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
         rng.next_u64()
@@ -175,12 +212,21 @@ impl Distribution<u64> for Uniform {
 
 #[cfg(feature = "i128_support")]
 impl Distribution<u128> for Uniform {
+    fill_via_bytes!(u128);
+
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u128 {
         rng.next_u128()
     }
 }
 
+impl<T> Distribution<Wrapping<T>> for Uniform where Uniform: Distribution<T> {
+    #[inline]
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> Wrapping<T> {
+        Wrapping(self.sample(rng))
+    }
+}
+
 impl Distribution<bool> for Uniform {
     #[inline]
     fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> bool {
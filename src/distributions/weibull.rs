@@ -0,0 +1,116 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Weibull distribution.
+
+use Rng;
+use distributions::{Distribution, Open01, Rand};
+
+/// The Weibull distribution `Weibull(scale, shape)`.
+///
+/// This distribution has density function:
+/// `f(x) = (shape / scale) * (x / scale)^(shape - 1) * exp(-(x / scale)^shape)`
+/// for `x >= 0`.
+///
+/// Samples are generated by inversion of the CDF.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Weibull, Distribution};
+///
+/// let weibull = Weibull::new(1.0, 1.5);
+/// let v = weibull.sample(&mut rand::thread_rng());
+/// println!("{} is from a Weibull(1, 1.5) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Weibull {
+    inv_shape: f64,
+    scale: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Weibull` distribution.
+    pub enum WeibullError {
+        /// `scale` is NaN or infinite.
+        ScaleNotFinite => "`scale` is NaN or infinite",
+        /// `scale <= 0`.
+        ScaleNotPositive => "`scale` is not positive",
+        /// `shape` is NaN or infinite.
+        ShapeNotFinite => "`shape` is NaN or infinite",
+        /// `shape <= 0`.
+        ShapeNotPositive => "`shape` is not positive",
+    }
+}
+
+impl Weibull {
+    /// Construct a new `Weibull` with the given `scale` and `shape`
+    /// parameters. Panics if either is not finite and positive; see
+    /// `try_new`.
+    pub fn new(scale: f64, shape: f64) -> Weibull {
+        Weibull::try_new(scale, shape).unwrap_or_else(|e| panic!("Weibull::new: {}", e))
+    }
+
+    /// Construct a new `Weibull`, or return an error if `scale` or `shape`
+    /// is NaN or infinite (`ScaleNotFinite`, `ShapeNotFinite`) or `<= 0`
+    /// (`ScaleNotPositive`, `ShapeNotPositive`).
+    pub fn try_new(scale: f64, shape: f64) -> Result<Weibull, WeibullError> {
+        if !scale.is_finite() {
+            return Err(WeibullError::ScaleNotFinite);
+        } else if scale <= 0.0 {
+            return Err(WeibullError::ScaleNotPositive);
+        } else if !shape.is_finite() {
+            return Err(WeibullError::ShapeNotFinite);
+        } else if shape <= 0.0 {
+            return Err(WeibullError::ShapeNotPositive);
+        }
+        Ok(Weibull { inv_shape: 1. / shape, scale })
+    }
+}
+
+impl Distribution<f64> for Weibull {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> f64 {
+        let x = f64::rand(rng, Open01);
+        self.scale * (-x.ln()).powf(self.inv_shape)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use distributions::test::assert_quantiles;
+    use super::{Weibull, WeibullError};
+
+    #[test]
+    fn test_weibull_quantiles() {
+        for &(scale, shape) in [(1.0, 1.0), (1.0, 1.5), (10.0, 0.2), (0.5, 20.0)].iter() {
+            assert_quantiles(Weibull::new(scale, shape),
+                             |p| scale * (-(-p).ln_1p()).powf(1. / shape));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_weibull_invalid_scale() {
+        Weibull::new(0.0, 1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_weibull_invalid_shape() {
+        Weibull::new(1.0, -1.0);
+    }
+    #[test]
+    fn test_weibull_try_new() {
+        assert_eq!(Weibull::try_new(f64::NAN, 1.0).unwrap_err(), WeibullError::ScaleNotFinite);
+        assert_eq!(Weibull::try_new(1.0, f64::INFINITY).unwrap_err(),
+                   WeibullError::ShapeNotFinite);
+        assert_eq!(Weibull::try_new(1.0, 0.0).unwrap_err(), WeibullError::ShapeNotPositive);
+    }
+}
@@ -11,7 +11,7 @@
 // Tables for distributions which are sampled using the ziggurat
 // algorithm. Autogenerated by `ziggurat_tables.py`.
 
-pub type ZigTable = &'static [f64; 257];
+pub type ZigTable<F> = &'static [F; 257];
 pub const ZIG_NORM_R: f64 = 3.654152885361008796;
 pub static ZIG_NORM_X: [f64; 257] =
     [3.910757959537090045, 3.654152885361008796, 3.449278298560964462, 3.320244733839166074,
@@ -278,3 +278,184 @@ pub static ZIG_EXP_F: [f64; 257] =
      0.775956852040116218, 0.791527636972496285, 0.808421651523009044, 0.826993296643051101,
      0.847785500623990496, 0.871704332381204705, 0.900469929925747703, 0.938143680862176477,
      1.000000000000000000];
+
+// The same tables rounded to `f32`, for sampling `f32` values natively.
+
+pub const ZIG_NORM_R_F32: f32 = 3.6541529;
+pub static ZIG_NORM_X_F32: [f32; 257] =
+    [3.910758, 3.6541529, 3.4492784, 3.3202448, 3.224575, 3.1478894,
+     3.0835261, 3.0278378, 2.9786034, 2.934367, 2.8941212, 2.8571386,
+     2.8228774, 2.7909212, 2.760944, 2.7326853, 2.7059336, 2.6805146,
+     2.6562831, 2.6331165, 2.6109104, 2.589576, 2.5690355, 2.5492215,
+     2.5300753, 2.5115445, 2.493583, 2.47615, 2.4592085, 2.4427254,
+     2.426671, 2.4110184, 2.3957431, 2.380823, 2.3662372, 2.3519673,
+     2.3379962, 2.324308, 2.3108883, 2.2977233, 2.2848008, 2.272109,
+     2.259637, 2.247375, 2.2353134, 2.2234433, 2.2117567, 2.2002456,
+     2.1889029, 2.1777215, 2.166695, 2.1558177, 2.1450837, 2.1344872,
+     2.1240232, 2.113687, 2.1034741, 2.0933797, 2.0833998, 2.0735302,
+     2.0637674, 2.054108, 2.044548, 2.0350842, 2.025714, 2.0164337,
+     2.0072408, 1.9981325, 1.989106, 1.9801589, 1.9712887, 1.9624931,
+     1.9537697, 1.9451165, 1.9365314, 1.9280124, 1.9195573, 1.9111645,
+     1.9028322, 1.8945585, 1.8863418, 1.8781805, 1.870073, 1.8620176,
+     1.8540131, 1.8460579, 1.8381506, 1.83029, 1.8224746, 1.8147032,
+     1.8069746, 1.7992876, 1.791641, 1.7840337, 1.7764645, 1.7689325,
+     1.7614363, 1.7539753, 1.7465483, 1.7391542, 1.7317923, 1.7244616,
+     1.7171609, 1.7098897, 1.7026469, 1.6954317, 1.6882432, 1.6810807,
+     1.6739433, 1.6668303, 1.6597408, 1.6526742, 1.6456295, 1.6386062,
+     1.6316035, 1.6246206, 1.6176568, 1.6107116, 1.6037842, 1.5968738,
+     1.5899799, 1.5831017, 1.5762388, 1.5693902, 1.5625554, 1.555734,
+     1.548925, 1.5421282, 1.5353426, 1.5285677, 1.521803, 1.5150478,
+     1.5083016, 1.5015637, 1.4948335, 1.4881105, 1.481394, 1.4746835,
+     1.4679785, 1.4612782, 1.4545821, 1.4478897, 1.4412003, 1.4345133,
+     1.4278282, 1.4211444, 1.4144613, 1.4077783, 1.4010948, 1.3944101,
+     1.3877238, 1.3810352, 1.3743436, 1.3676486, 1.3609494, 1.3542453,
+     1.3475358, 1.3408203, 1.3340981, 1.3273686, 1.320631, 1.3138846,
+     1.307129, 1.3003632, 1.2935867, 1.2867987, 1.2799984, 1.2731853,
+     1.2663583, 1.2595168, 1.2526603, 1.2457875, 1.2388979, 1.2319906,
+     1.2250646, 1.2181194, 1.2111537, 1.2041669, 1.1971577, 1.1901255,
+     1.1830691, 1.1759876, 1.1688799, 1.1617448, 1.1545814, 1.1473885,
+     1.1401649, 1.1329093, 1.1256205, 1.1182972, 1.1109381, 1.1035417,
+     1.0961066, 1.0886314, 1.0811144, 1.073554, 1.0659487, 1.0582964,
+     1.0505956, 1.0428443, 1.0350405, 1.027182, 1.0192667, 1.0112925,
+     1.0032567, 0.995157, 0.98699075, 0.9787552, 0.9704473, 0.96206415,
+     0.95360243, 0.9450587, 0.9364293, 0.92771053, 0.91889817, 0.9099879,
+     0.9009752, 0.89185506, 0.88262224, 0.87327105, 0.8637955, 0.85418916,
+     0.84444493, 0.8345553, 0.8245122, 0.8143067, 0.8039291, 0.79336905,
+     0.782615, 0.7716544, 0.76047343, 0.74905664, 0.73738724, 0.72544616,
+     0.7132123, 0.70066184, 0.68776786, 0.6744998, 0.6608226, 0.64669573,
+     0.6320722, 0.616897, 0.6011046, 0.5846168, 0.5673382, 0.5491517,
+     0.52990973, 0.5094233, 0.48744395, 0.46363434, 0.4375184, 0.40838912,
+     0.37512133, 0.33573753, 0.2861746, 0.2152419, 0.0];
+pub static ZIG_NORM_F_F32: [f32; 257] =
+    [0.00047746775, 0.001260286, 0.0026090727, 0.0040379725, 0.0055224034, 0.0070508756,
+     0.0086165825, 0.010214971, 0.011842757, 0.01349745, 0.015177088, 0.016880084,
+     0.01860512, 0.020351097, 0.022117063, 0.023902204, 0.025705803, 0.027527235,
+     0.02936594, 0.031221418, 0.033093218, 0.03498094, 0.036884215, 0.038802706,
+     0.04073611, 0.042684145, 0.044646554, 0.046623096, 0.048613552, 0.050617725,
+     0.05263542, 0.05466646, 0.05671069, 0.058767952, 0.060838107, 0.062921025,
+     0.065016575, 0.06712466, 0.069245145, 0.07137795, 0.07352297, 0.07568013,
+     0.077849336, 0.080030516, 0.082223594, 0.08442851, 0.08664519, 0.088873595,
+     0.09111365, 0.09336531, 0.09562854, 0.09790328, 0.1001895, 0.10248716,
+     0.10479622, 0.10711667, 0.109448455, 0.111791566, 0.11414598, 0.116511665,
+     0.11888862, 0.1212768, 0.123676226, 0.12608688, 0.12850872, 0.13094178,
+     0.13338603, 0.13584147, 0.13830812, 0.14078595, 0.14327498, 0.14577521,
+     0.14828664, 0.15080929, 0.15334316, 0.15588826, 0.15844461, 0.16101222,
+     0.1635911, 0.16618128, 0.16878277, 0.1713956, 0.17401977, 0.17665532,
+     0.17930228, 0.18196066, 0.1846305, 0.18731181, 0.19000465, 0.19270904,
+     0.195425, 0.19815259, 0.20089182, 0.20364276, 0.2064054, 0.20917983,
+     0.21196608, 0.21476418, 0.21757418, 0.22039613, 0.22323008, 0.22607607,
+     0.22893417, 0.23180442, 0.23468687, 0.23758158, 0.2404886, 0.24340801,
+     0.24633986, 0.24928421, 0.25224113, 0.25521067, 0.2581929, 0.2611879,
+     0.26419577, 0.2672165, 0.27025026, 0.27329704, 0.276357, 0.27943015,
+     0.2825166, 0.28561643, 0.28872973, 0.2918566, 0.2949971, 0.2981513,
+     0.3013194, 0.30450138, 0.30769742, 0.31090757, 0.31413195, 0.31737062,
+     0.3206238, 0.3238915, 0.32717383, 0.33047098, 0.33378303, 0.33711007,
+     0.34045225, 0.34380972, 0.34718257, 0.35057095, 0.35397497, 0.3573948,
+     0.3608306, 0.36428246, 0.36775056, 0.37123504, 0.3747361, 0.37825382,
+     0.3817884, 0.38534003, 0.38890886, 0.39249507, 0.39609882, 0.3997203,
+     0.40335974, 0.4070173, 0.41069314, 0.41438752, 0.41810066, 0.4218327,
+     0.42558393, 0.42935455, 0.43314478, 0.43695486, 0.44078502, 0.44463557,
+     0.4485067, 0.45239872, 0.45631185, 0.4602464, 0.4642027, 0.46818095,
+     0.47218153, 0.47620472, 0.48025087, 0.48432028, 0.48841327, 0.49253026,
+     0.49667156, 0.50083756, 0.50502867, 0.5092452, 0.5134877, 0.5177565,
+     0.52205205, 0.5263749, 0.5307253, 0.5351039, 0.53951126, 0.54394776,
+     0.548414, 0.5529105, 0.5574379, 0.56199676, 0.56658775, 0.5712115,
+     0.57586867, 0.58055997, 0.5852862, 0.590048, 0.59484625, 0.59968174,
+     0.60455537, 0.60946804, 0.6144207, 0.6194144, 0.62445, 0.62952876,
+     0.6346518, 0.6398203, 0.6450355, 0.6502987, 0.65561146, 0.66097516,
+     0.6663914, 0.6718617, 0.677388, 0.68297213, 0.6886161, 0.69432193,
+     0.7000919, 0.7059285, 0.71183425, 0.71781194, 0.72386456, 0.72999525,
+     0.7362076, 0.7425053, 0.7488924, 0.7553735, 0.76195335, 0.7686373,
+     0.7754313, 0.78234184, 0.78937614, 0.79654235, 0.80384946, 0.81130785,
+     0.8189292, 0.82672685, 0.8347163, 0.84291565, 0.85134625, 0.86003363,
+     0.86900866, 0.87830967, 0.88798463, 0.8980959, 0.90872645, 0.9199915,
+     0.93206006, 0.94519895, 0.9598791, 0.9771017, 1.0];
+pub const ZIG_EXP_R_F32: f32 = 7.6971173;
+pub static ZIG_EXP_X_F32: [f32; 257] =
+    [8.697118, 7.6971173, 6.941034, 6.4783783, 6.1441646, 5.8821445,
+     5.66641, 5.4828906, 5.3230906, 5.181487, 5.0542884, 4.938777,
+     4.8329396, 4.735243, 4.6444917, 4.559737, 4.4802117, 4.4052877,
+     4.3344436, 4.2672424, 4.203314, 4.1423407, 4.084051, 4.0282087,
+     3.974606, 3.9230626, 3.8734176, 3.8255293, 3.779271, 3.7345288,
+     3.691201, 3.6491954, 3.6084287, 3.5688252, 3.5303159, 3.4928377,
+     3.456333, 3.4207485, 3.3860354, 3.352149, 3.3190475, 3.2866921,
+     3.2550473, 3.2240796, 3.193758, 3.1640534, 3.134939, 3.106389,
+     3.07838, 3.05089, 3.0238974, 2.9973829, 2.9713278, 2.9457145,
+     2.9205263, 2.8957477, 2.871364, 2.8473608, 2.8237252, 2.8004444,
+     2.777506, 2.7548993, 2.7326126, 2.7106361, 2.6889596, 2.667574,
+     2.64647, 2.625639, 2.605073, 2.5847638, 2.5647042, 2.5448866,
+     2.5253043, 2.5059507, 2.4868193, 2.467904, 2.449199, 2.4306984,
+     2.412397, 2.394289, 2.3763702, 2.358635, 2.3410792, 2.3236978,
+     2.3064868, 2.2894418, 2.272559, 2.2558339, 2.2392628, 2.2228425,
+     2.206569, 2.190439, 2.174449, 2.1585958, 2.1428764, 2.1272876,
+     2.1118267, 2.0964901, 2.081276, 2.0661807, 2.0512023, 2.036338,
+     2.0215852, 2.0069418, 1.9924049, 1.9779727, 1.9636427, 1.9494127,
+     1.9352808, 1.9212447, 1.9073025, 1.8934522, 1.8796918, 1.8660195,
+     1.8524336, 1.8389319, 1.8255131, 1.8121753, 1.7989168, 1.785736,
+     1.7726312, 1.7596009, 1.7466437, 1.7337579, 1.720942, 1.7081947,
+     1.6955146, 1.6829001, 1.67035, 1.6578629, 1.6454375, 1.6330724,
+     1.6207665, 1.6085185, 1.5963271, 1.5841911, 1.5721092, 1.5600805,
+     1.5481036, 1.5361774, 1.5243009, 1.5124729, 1.5006921, 1.4889578,
+     1.4772687, 1.4656237, 1.4540218, 1.4424621, 1.4309433, 1.4194646,
+     1.4080249, 1.3966233, 1.3852586, 1.37393, 1.3626364, 1.3513769,
+     1.3401506, 1.3289564, 1.3177934, 1.3066607, 1.2955571, 1.284482,
+     1.2734343, 1.2624129, 1.2514172, 1.2404459, 1.2294981, 1.2185732,
+     1.2076699, 1.1967874, 1.1859246, 1.1750807, 1.1642547, 1.1534455,
+     1.1426523, 1.131874, 1.1211096, 1.1103581, 1.0996186, 1.08889,
+     1.0781711, 1.0674613, 1.056759, 1.0460634, 1.0353734, 1.0246879,
+     1.0140057, 1.0033256, 0.9926464, 0.98196703, 0.97128624, 0.9606027,
+     0.9499152, 0.93922234, 0.92852277, 0.9178152, 0.90709805, 0.89637,
+     0.8856295, 0.8748749, 0.8641046, 0.853317, 0.84251034, 0.83168286,
+     0.8208326, 0.80995774, 0.7990562, 0.7881259, 0.77716464, 0.7661701,
+     0.75514, 0.7440717, 0.73296267, 0.7218101, 0.71061105, 0.69936246,
+     0.6880611, 0.6767036, 0.6652861, 0.65380496, 0.64225596, 0.63063467,
+     0.6189365, 0.6071562, 0.5952886, 0.5833277, 0.5712673, 0.55910057,
+     0.5468201, 0.53441787, 0.52188504, 0.50921196, 0.49638805, 0.48340148,
+     0.47023928, 0.45688683, 0.44332787, 0.42954394, 0.41551417, 0.4012147,
+     0.386618, 0.37169215, 0.35639977, 0.34069648, 0.3245291, 0.30783296,
+     0.29052797, 0.27251318, 0.25365835, 0.23379049, 0.2126715, 0.18995869,
+     0.16512762, 0.13730498, 0.104838505, 0.06385216, 0.0];
+pub static ZIG_EXP_F_F32: [f32; 257] =
+    [0.0001670667, 0.00045413437, 0.0009672693, 0.0015362998, 0.0021459677, 0.0027887989,
+     0.0034602648, 0.004157295, 0.0048776558, 0.0056196423, 0.006381906, 0.0071633533,
+     0.007963077, 0.008780315, 0.009614414, 0.01046481, 0.011331013, 0.012212592,
+     0.013109165, 0.014020392, 0.014945968, 0.015885621, 0.016839107, 0.0178062,
+     0.0187867, 0.019780423, 0.020787204, 0.021806888, 0.022839336, 0.023884421,
+     0.024942026, 0.026012046, 0.027094385, 0.02818895, 0.02929566, 0.030414443,
+     0.031545233, 0.032687962, 0.033842582, 0.035009038, 0.036187284, 0.037377283,
+     0.038578995, 0.039792392, 0.041017443, 0.042254124, 0.043502413, 0.0447623,
+     0.046033762, 0.047316793, 0.048611384, 0.049917534, 0.051235236, 0.052564494,
+     0.053905312, 0.05525769, 0.05662164, 0.057997175, 0.059384305, 0.060783047,
+     0.062193416, 0.063615434, 0.06504912, 0.066494495, 0.06795159, 0.069420435,
+     0.07090106, 0.072393484, 0.07389775, 0.07541389, 0.076941945, 0.07848195,
+     0.08003395, 0.081597984, 0.083174095, 0.08476233, 0.08636274, 0.087975375,
+     0.08960028, 0.091237515, 0.09288713, 0.09454919, 0.09622374, 0.09791085,
+     0.09961058, 0.101323, 0.10304816, 0.10478614, 0.10653701, 0.10830083,
+     0.11007768, 0.11186763, 0.11367077, 0.115487166, 0.1173169, 0.119160056,
+     0.12101672, 0.12288698, 0.12477092, 0.12666863, 0.1285802, 0.13050574,
+     0.13244532, 0.13439907, 0.13636707, 0.13834943, 0.14034624, 0.14235765,
+     0.14438373, 0.14642459, 0.14848037, 0.15055119, 0.15263714, 0.15473837,
+     0.15685499, 0.15898713, 0.16113494, 0.16329853, 0.16547804, 0.16767362,
+     0.1698854, 0.17211354, 0.17435817, 0.17661946, 0.17889754, 0.1811926,
+     0.18350479, 0.18583426, 0.1881812, 0.19054577, 0.19292815, 0.19532852,
+     0.19774707, 0.20018397, 0.20263945, 0.20511365, 0.20760682, 0.21011916,
+     0.21265087, 0.21520215, 0.21777324, 0.22036438, 0.22297576, 0.22560766,
+     0.2282603, 0.23093392, 0.23362878, 0.23634516, 0.23908329, 0.24184346,
+     0.24462597, 0.24743107, 0.25025907, 0.2531103, 0.25598502, 0.25888354,
+     0.26180625, 0.26475343, 0.2677254, 0.2707226, 0.2737453, 0.27679393,
+     0.27986884, 0.28297043, 0.28609908, 0.28925523, 0.29243928, 0.2956517,
+     0.29889292, 0.3021634, 0.3054636, 0.30879408, 0.31215525, 0.31554767,
+     0.3189719, 0.3224285, 0.32591796, 0.32944095, 0.33299807, 0.3365899,
+     0.34021714, 0.34388044, 0.3475805, 0.351318, 0.35509375, 0.35890847,
+     0.362763, 0.3666581, 0.37059465, 0.37457356, 0.37859577, 0.38266218,
+     0.38677382, 0.39093173, 0.39513698, 0.3993907, 0.403694, 0.40804818,
+     0.41245446, 0.4169142, 0.42142874, 0.42599955, 0.43062815, 0.43531612,
+     0.44006512, 0.44487688, 0.44975325, 0.45469615, 0.45970762, 0.46478975,
+     0.46994483, 0.4751752, 0.48048335, 0.485872, 0.49134386, 0.496902,
+     0.5025495, 0.50828975, 0.5141264, 0.52006316, 0.5261042, 0.53225386,
+     0.5385169, 0.5448982, 0.5514034, 0.5580383, 0.5648092, 0.57172304,
+     0.5787874, 0.58601034, 0.5934009, 0.60096896, 0.60872537, 0.6166822,
+     0.6248527, 0.63325197, 0.6418967, 0.65080583, 0.66000086, 0.6695063,
+     0.67935055, 0.6895665, 0.70019263, 0.71127474, 0.72286767, 0.7350381,
+     0.7478686, 0.7614634, 0.77595687, 0.7915276, 0.8084217, 0.8269933,
+     0.8477855, 0.87170434, 0.90046996, 0.9381437, 1.0];
@@ -0,0 +1,294 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Zipf and Zeta power-law distributions.
+
+use Rng;
+use distributions::{Distribution, Uniform01, Open01, Rand};
+
+/// The Zipf distribution `Zipf(n, s)` on the integers `1..=n`.
+///
+/// This distribution has probability mass function:
+/// `f(k) = k^(-s) / H(n, s)` for `1 <= k <= n`, where `H(n, s)` is the
+/// generalised harmonic number `sum_{i=1}^n i^(-s)`.
+///
+/// Samples are generated by rejection from a continuous envelope whose CDF is
+/// inverted in closed form, so setup takes constant time and sampling takes
+/// constant expected time for any `n`.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Zipf, Distribution};
+///
+/// // the popularity rank of a key among a billion keys
+/// let zipf = Zipf::new(1_000_000_000, 1.1);
+/// let v = zipf.sample(&mut rand::thread_rng());
+/// println!("{} is from a Zipf distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Zipf {
+    n: u64,
+    s: f64,
+    /// The total mass of the envelope.
+    t: f64,
+    /// `1 / (1 - s)`, unused if `s == 1`
+    q: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Zipf` distribution.
+    pub enum ZipfError {
+        /// `n < 1`.
+        NTooSmall => "`n` is less than 1",
+        /// `s` is NaN or infinite.
+        SNotFinite => "`s` is NaN or infinite",
+        /// `s < 0`.
+        SNegative => "`s` is negative",
+    }
+}
+
+impl Zipf {
+    /// Construct a new `Zipf` over `1..=n` with exponent `s`.
+    ///
+    /// Panics if `n < 1`, or `s` is not finite or `s < 0`; see `try_new`.
+    /// With `s == 0` the distribution is uniform.
+    pub fn new(n: u64, s: f64) -> Zipf {
+        Zipf::try_new(n, s).unwrap_or_else(|e| panic!("Zipf::new: {}", e))
+    }
+
+    /// Construct a new `Zipf` over `1..=n` with exponent `s`, or return an
+    /// error if `n < 1` (`NTooSmall`), `s` is NaN or infinite
+    /// (`SNotFinite`) or `s < 0` (`SNegative`).
+    pub fn try_new(n: u64, s: f64) -> Result<Zipf, ZipfError> {
+        if n < 1 {
+            return Err(ZipfError::NTooSmall);
+        } else if !s.is_finite() {
+            return Err(ZipfError::SNotFinite);
+        } else if s < 0.0 {
+            return Err(ZipfError::SNegative);
+        }
+        let n_f = n as f64;
+        let (t, q) = if s == 1.0 {
+            (1. + n_f.ln(), 0.)
+        } else {
+            ((n_f.powf(1. - s) - s) / (1. - s), 1. / (1. - s))
+        };
+        Ok(Zipf { n, s, t, q })
+    }
+
+    /// The inverse CDF of the envelope, which has density `1` on `[0, 1]`
+    /// and `x^(-s)` on `[1, n]`.
+    fn inv_cdf(&self, p: f64) -> f64 {
+        let pt = p * self.t;
+        if pt <= 1. {
+            pt
+        } else if self.s == 1.0 {
+            (pt - 1.).exp()
+        } else {
+            (pt * (1. - self.s) + self.s).powf(self.q)
+        }
+    }
+}
+
+impl Distribution<u64> for Zipf {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        loop {
+            // A point `x` of the envelope is mapped to `k = floor(x) + 1`.
+            // Since the envelope is at least `k^(-s)` on `[k - 1, k)`,
+            // accepting with probability `k^(-s) / envelope(x)` yields `k`
+            // with probability proportional to `k^(-s)`.
+            let x = self.inv_cdf(f64::rand(rng, Uniform01));
+            let k = (x + 1.).floor();
+            if k > self.n as f64 {
+                // only possible through rounding
+                continue;
+            }
+            let mut ratio = k.powf(-self.s);
+            if k > 1. {
+                ratio *= x.powf(self.s);
+            }
+            if f64::rand(rng, Uniform01) < ratio {
+                return k as u64;
+            }
+        }
+    }
+}
+
+/// The Zeta distribution `Zeta(s)` on the positive integers, the limit of
+/// `Zipf(n, s)` as `n` tends to infinity.
+///
+/// This distribution has probability mass function:
+/// `f(k) = k^(-s) / ζ(s)` for `k >= 1`, where `ζ` is the Riemann zeta
+/// function.
+///
+/// Samples are generated with Devroye's rejection algorithm[1], which needs
+/// no evaluation of `ζ`. Values too large for a `u64`, which are only
+/// possible for `s` close to 1, saturate to `u64::MAX`.
+///
+/// [1]: Luc Devroye (1986). [*Non-Uniform Random Variate
+/// Generation*](http://luc.devroye.org/rnbookindex.html), chapter X.6.1.
+/// Springer-Verlag, New York.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{Zeta, Distribution};
+///
+/// let zeta = Zeta::new(1.5);
+/// let v = zeta.sample(&mut rand::thread_rng());
+/// println!("{} is from a Zeta(1.5) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Zeta {
+    /// `s - 1`
+    s_minus_1: f64,
+    /// `2^(s - 1)`
+    b: f64,
+}
+
+parameter_error! {
+    /// An error constructing a `Zeta` distribution.
+    pub enum ZetaError {
+        /// `s` is NaN or infinite.
+        SNotFinite => "`s` is NaN or infinite",
+        /// `s <= 1`.
+        STooSmall => "`s` is not greater than 1",
+    }
+}
+
+impl Zeta {
+    /// Construct a new `Zeta` with exponent `s`. Panics if `s` is not
+    /// finite or `s <= 1`; see `try_new`.
+    pub fn new(s: f64) -> Zeta {
+        Zeta::try_new(s).unwrap_or_else(|e| panic!("Zeta::new: {}", e))
+    }
+
+    /// Construct a new `Zeta` with exponent `s`, or return an error if `s`
+    /// is NaN or infinite (`SNotFinite`) or `s <= 1` (`STooSmall`).
+    pub fn try_new(s: f64) -> Result<Zeta, ZetaError> {
+        if !s.is_finite() {
+            return Err(ZetaError::SNotFinite);
+        } else if s <= 1.0 {
+            return Err(ZetaError::STooSmall);
+        }
+        Ok(Zeta { s_minus_1: s - 1., b: 2f64.powf(s - 1.) })
+    }
+}
+
+impl Distribution<u64> for Zeta {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> u64 {
+        loop {
+            let u = f64::rand(rng, Open01);
+            let x = u.powf(-1. / self.s_minus_1).floor();
+            if !x.is_finite() {
+                continue;
+            }
+            let t = (1. + 1. / x).powf(self.s_minus_1);
+            let v = f64::rand(rng, Uniform01);
+            if v * x * (t - 1.) * self.b <= t * (self.b - 1.) {
+                return x as u64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+    use SeedableRng;
+    use distributions::Distribution;
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{Zipf, Zeta, ZipfError, ZetaError};
+
+    fn test_zipf_fit(n: u64, s: f64) {
+        let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+        let zipf = Zipf::new(n, s);
+        let samples: Vec<u64> = (0..20_000).map(|_| zipf.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&k| k >= 1 && k <= n));
+        let h: f64 = (1..n + 1).map(|i| (i as f64).powf(-s)).sum();
+        assert_discrete_fit(&samples, |k| {
+            if k == 0 || k > n { 0. } else { (k as f64).powf(-s) / h }
+        });
+    }
+
+    #[test]
+    fn test_zipf() {
+        test_zipf_fit(1, 1.0);
+        test_zipf_fit(10, 0.0);
+        test_zipf_fit(10, 1.0);
+        test_zipf_fit(100, 0.5);
+        test_zipf_fit(1000, 1.5);
+        test_zipf_fit(100_000, 2.0);
+    }
+
+    #[test]
+    fn test_zipf_large_n() {
+        let mut rng = ::test::rng();
+        let n = 10_000_000_000;
+        let zipf = Zipf::new(n, 1.2);
+        for _ in 0..1000 {
+            let k = zipf.sample(&mut rng);
+            assert!(k >= 1 && k <= n);
+        }
+    }
+
+    /// The Riemann zeta function, by Euler-Maclaurin summation.
+    fn zeta(s: f64) -> f64 {
+        let n = 1000f64;
+        let sum: f64 = (1..1000).map(|i| (i as f64).powf(-s)).sum();
+        sum + n.powf(1. - s) / (s - 1.) + 0.5 * n.powf(-s) + s / 12. * n.powf(-s - 1.)
+    }
+
+    #[test]
+    fn test_zeta() {
+        // ζ(2) = π²/6
+        assert!((zeta(2.0) - 1.6449340668482264).abs() < 1e-12);
+        for &s in [1.5, 2.0, 3.5, 10.0].iter() {
+            let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+            let z = Zeta::new(s);
+            let samples: Vec<u64> = (0..20_000).map(|_| z.sample(&mut rng)).collect();
+            assert!(samples.iter().all(|&k| k >= 1));
+            let norm = zeta(s);
+            assert_discrete_fit(&samples, |k| {
+                if k == 0 { 0. } else { (k as f64).powf(-s) / norm }
+            });
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zipf_invalid_n() {
+        Zipf::new(0, 1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_zipf_invalid_s() {
+        Zipf::new(10, -1.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_zeta_invalid_s() {
+        Zeta::new(1.0);
+    }
+    #[test]
+    fn test_zipf_try_new() {
+        assert!(Zipf::try_new(1, 0.0).is_ok());
+        assert_eq!(Zipf::try_new(0, 1.0).unwrap_err(), ZipfError::NTooSmall);
+        assert_eq!(Zipf::try_new(10, f64::NAN).unwrap_err(), ZipfError::SNotFinite);
+        assert_eq!(Zipf::try_new(10, -1.0).unwrap_err(), ZipfError::SNegative);
+    }
+    #[test]
+    fn test_zeta_try_new() {
+        assert_eq!(Zeta::try_new(f64::INFINITY).unwrap_err(), ZetaError::SNotFinite);
+        assert_eq!(Zeta::try_new(1.0).unwrap_err(), ZetaError::STooSmall);
+    }
+}
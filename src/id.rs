@@ -0,0 +1,252 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random identifiers: version 4 UUIDs and fixed-length tokens.
+//!
+//! Identifiers such as session tokens and nonces are only safe to use if
+//! they can not be predicted, so all generators in this module require a
+//! `CryptoRng`, such as `OsRng` or `ChaChaRng`. To build an identifier from
+//! random bytes of another source, use `Uuid::from_random_bytes`, or encode
+//! the bytes directly.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rand::OsRng;
+//! use rand::id::{Uuid, hex_token, base64url_token};
+//!
+//! let mut rng = OsRng::new().unwrap();
+//! let request_id = Uuid::new_v4(&mut rng);
+//! println!("{}", request_id);
+//!
+//! let session = base64url_token(&mut rng, 32);
+//! let nonce = hex_token(&mut rng, 12);
+//! assert_eq!((session.len(), nonce.len()), (43, 24));
+//! ```
+//!
+//! A generator that is not a `CryptoRng` is rejected at compile time:
+//!
+//! ```rust,compile_fail
+//! use rand::NewSeeded;
+//! use rand::prng::XorShiftRng;
+//! use rand::id::Uuid;
+//!
+//! let mut rng = XorShiftRng::new().unwrap();
+//! let uuid = Uuid::new_v4(&mut rng);
+//! ```
+
+use core::fmt;
+
+use CryptoRng;
+
+/// A random (version 4) UUID, as specified by [RFC 4122].
+///
+/// `Display` formats the UUID hyphenated, like
+/// `3f9c5e1a-7b2d-4c8e-9a6f-0d1e2b3c4d5e`.
+///
+/// [RFC 4122]: https://tools.ietf.org/html/rfc4122#section-4.4
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid {
+    bytes: [u8; 16],
+}
+
+impl Uuid {
+    /// Generate a random UUID.
+    pub fn new_v4<R: CryptoRng+?Sized>(rng: &mut R) -> Uuid {
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        Uuid::from_random_bytes(bytes)
+    }
+
+    /// Build a UUID from 16 random bytes. Six of the bits are replaced by the
+    /// version (4) and variant (`10` in binary) fields, the others are kept.
+    pub fn from_random_bytes(mut bytes: [u8; 16]) -> Uuid {
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        Uuid { bytes }
+    }
+
+    /// The 16 bytes of the UUID, in the order they are formatted.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.bytes
+    }
+
+    /// Format as 32 hexadecimal digits in groups of 8, 4, 4, 4 and 12,
+    /// separated by hyphens. This is the format of `Display`.
+    pub fn hyphenated(&self) -> Hyphenated {
+        Hyphenated { bytes: self.bytes }
+    }
+
+    /// Format as 32 hexadecimal digits without hyphens.
+    pub fn simple(&self) -> Simple {
+        Simple { bytes: self.bytes }
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.hyphenated().fmt(f)
+    }
+}
+
+/// A `Uuid` formatted with hyphens; see `Uuid::hyphenated`.
+#[derive(Clone, Copy, Debug)]
+pub struct Hyphenated {
+    bytes: [u8; 16],
+}
+
+/// A `Uuid` formatted without hyphens; see `Uuid::simple`.
+#[derive(Clone, Copy, Debug)]
+pub struct Simple {
+    bytes: [u8; 16],
+}
+
+impl fmt::Display for Hyphenated {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, b) in self.bytes.iter().enumerate() {
+            if i == 4 || i == 6 || i == 8 || i == 10 {
+                f.write_str("-")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Simple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in self.bytes.iter() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Generate `n_bytes` random bytes and encode them as lower case hexadecimal
+/// digits, giving a string of `2 * n_bytes` characters.
+#[cfg(feature="std")]
+pub fn hex_token<R: CryptoRng+?Sized>(rng: &mut R, n_bytes: usize) -> String {
+    encode_hex(&random_bytes(rng, n_bytes))
+}
+
+/// Generate `n_bytes` random bytes and encode them with the URL and filename
+/// safe base64 alphabet of [RFC 4648], without padding. The string has
+/// `ceil(4 * n_bytes / 3)` characters.
+///
+/// [RFC 4648]: https://tools.ietf.org/html/rfc4648#section-5
+#[cfg(feature="std")]
+pub fn base64url_token<R: CryptoRng+?Sized>(rng: &mut R, n_bytes: usize) -> String {
+    encode_base64url(&random_bytes(rng, n_bytes))
+}
+
+#[cfg(feature="std")]
+fn random_bytes<R: CryptoRng+?Sized>(rng: &mut R, n_bytes: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; n_bytes];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+#[cfg(feature="std")]
+fn encode_hex(bytes: &[u8]) -> String {
+    const HEX_DIGITS: &[u8] = b"0123456789abcdef";
+    let mut s = String::with_capacity(2 * bytes.len());
+    for &b in bytes {
+        s.push(HEX_DIGITS[(b >> 4) as usize] as char);
+        s.push(HEX_DIGITS[(b & 0xf) as usize] as char);
+    }
+    s
+}
+
+#[cfg(feature="std")]
+fn encode_base64url(bytes: &[u8]) -> String {
+    const BASE64URL_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                                     abcdefghijklmnopqrstuvwxyz\
+                                     0123456789-_";
+    let mut s = String::with_capacity(4 * bytes.len() / 3 + 1);
+    for chunk in bytes.chunks(3) {
+        // Pack the chunk into the high bits of 24, and emit a character for
+        // each 6 bits that contain data.
+        let mut group = 0u32;
+        for (i, &b) in chunk.iter().enumerate() {
+            group |= (b as u32) << (16 - 8 * i);
+        }
+        for i in 0..(chunk.len() + 1) {
+            let index = (group >> (18 - 6 * i)) & 0x3f;
+            s.push(BASE64URL_CHARS[index as usize] as char);
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use SeedableRng;
+    use prng::ChaChaRng;
+    use super::{Uuid, hex_token, base64url_token, encode_hex, encode_base64url};
+
+    #[test]
+    fn test_uuid_format() {
+        let uuid = Uuid::from_random_bytes([0; 16]);
+        assert_eq!(uuid.to_string(), "00000000-0000-4000-8000-000000000000");
+        let uuid = Uuid::from_random_bytes([0xff; 16]);
+        assert_eq!(uuid.to_string(), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+        assert_eq!(uuid.hyphenated().to_string(), uuid.to_string());
+        assert_eq!(uuid.simple().to_string(), "ffffffffffff4fffbfffffffffffffff");
+
+        let bytes = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+                     0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10];
+        let uuid = Uuid::from_random_bytes(bytes);
+        assert_eq!(uuid.to_string(), "01234567-89ab-4def-bedc-ba9876543210");
+        assert_eq!(uuid.as_bytes()[..6], bytes[..6]);
+        assert_eq!(uuid.as_bytes()[9..], bytes[9..]);
+    }
+
+    #[test]
+    fn test_uuid_new_v4() {
+        let mut rng = ChaChaRng::from_seed(&[1, 2, 3, 4][..]);
+        let a = Uuid::new_v4(&mut rng);
+        let b = Uuid::new_v4(&mut rng);
+        assert!(a != b);
+        for uuid in [a, b].iter() {
+            assert_eq!(uuid.as_bytes()[6] >> 4, 4);
+            assert_eq!(uuid.as_bytes()[8] >> 6, 0b10);
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode_hex(&[]), "");
+        assert_eq!(encode_hex(&[0x00, 0x9f, 0xa0, 0xff]), "009fa0ff");
+
+        // the test vectors of RFC 4648, without padding
+        let vectors = [("", ""), ("f", "Zg"), ("fo", "Zm8"), ("foo", "Zm9v"),
+                       ("foob", "Zm9vYg"), ("fooba", "Zm9vYmE"), ("foobar", "Zm9vYmFy")];
+        for &(input, output) in vectors.iter() {
+            assert_eq!(encode_base64url(input.as_bytes()), output);
+        }
+        // the characters that differ from standard base64
+        assert_eq!(encode_base64url(&[0xfb, 0xff]), "-_8");
+    }
+
+    #[test]
+    fn test_tokens() {
+        let mut rng = ChaChaRng::from_seed(&[1, 2, 3, 4][..]);
+        for n in 0..40 {
+            let hex = hex_token(&mut rng, n);
+            assert_eq!(hex.len(), 2 * n);
+            assert!(hex.bytes().all(|b| b.is_ascii_digit() || (b >= b'a' && b <= b'f')));
+
+            let base64 = base64url_token(&mut rng, n);
+            assert_eq!(base64.len(), 4 * n / 3 + if n % 3 == 0 { 0 } else { 1 });
+            assert!(base64.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+        }
+        assert!(hex_token(&mut rng, 16) != hex_token(&mut rng, 16));
+    }
+}
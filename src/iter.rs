@@ -13,6 +13,9 @@
 //! Lifetime restrictions prevent an `Rng` iterator from simply implementing
 //! `std::iter::Iterator`. Instead, you get the simplified iterators below,
 //! providing only a subset of functionality.
+//! 
+//! To sample values from a distribution, prefer `Sample::sample_iter`, which
+//! does return a real `Iterator`.
 
 use core::cmp::min;
 use core::usize;
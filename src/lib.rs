@@ -266,13 +266,20 @@ pub use distributions::{Distribution, Default, Rand};
 #[cfg(feature="std")]
 pub use thread_local::{ThreadRng, thread_rng, random, random_with};
 
+use core::{mem, slice};
+
 use prng::IsaacWordRng;
-use distributions::range::Range;
 
+pub mod counting;
+#[macro_use]
 pub mod distributions;
+pub mod id;
 pub mod iter;
+#[cfg(any(test, feature="mock"))]
 pub mod mock;
 pub mod prng;
+#[cfg(feature="std")]
+pub mod recording;
 pub mod reseeding;
 pub mod sequences;
 pub mod utils;
@@ -305,12 +312,12 @@ impl<R: SeedFromRng> NewSeeded for R {
     }
 }
 
-use distributions::range::SampleRange;
+use distributions::range::SampleBounds;
 
 /// Extension trait on [`Rng`] with some convenience methods.
 /// 
 /// This trait exists to allow syntax like `rng.gen()` and
-/// `rng.gen_range(1, 7)`. None of the methods in this trait are any more than
+/// `rng.gen_range(1..7)`. None of the methods in this trait are any more than
 /// wrappers around functionality which exists elsewhere in the trait.
 pub trait Sample: Rng {
     /// Sample a new value, using the given distribution.
@@ -340,7 +347,10 @@ pub trait Sample: Rng {
         self.sample(Default)
     }
     
-    /// Sample a new value, using the [`Range`] distribution.
+    /// Sample a new value from the range `low..high` or `low..=high`, using
+    /// the [`Range`] distribution.
+    ///
+    /// Panics if the range is empty.
     /// 
     /// ### Example
     /// 
@@ -350,11 +360,16 @@ pub trait Sample: Rng {
     /// let mut rng = thread_rng();
     /// 
     /// // simulate dice roll
-    /// let x = rng.gen_range(1, 7);
+    /// let x = rng.gen_range(1..7);
+    /// let y = rng.gen_range(1..=6);
+    ///
+    /// // inclusive ranges can cover the whole type
+    /// let byte = rng.gen_range(0..=255u8);
     /// ```
     /// 
-    /// If the same range is used repeatedly, some work can be saved by
-    /// constructing the `Range` once and using it with `sample`:
+    /// This uses `Range::sample_single`, which is the fastest way to take a
+    /// single sample. If the same range is used repeatedly, some work can be
+    /// saved by constructing the `Range` once and using it with `sample`:
     /// 
     /// ```rust
     /// use rand::{thread_rng, Sample};
@@ -368,13 +383,124 @@ pub trait Sample: Rng {
     ///     assert!(1 <= x && x <= 6);
     /// }
     /// ```
-    fn gen_range<T: SampleRange>(&mut self, low: T, high: T) -> T {
-        assert!(low < high, "Sample::gen_range called with low >= high");
-        Range::new(low, high).sample(self)
+    ///
+    /// [`Range`]: distributions/range/struct.Range.html
+    fn gen_range<T, B: SampleBounds<T>>(&mut self, range: B) -> T {
+        range.sample_single(self)
+    }
+    
+    /// Generate a `String` of `len` characters sampled from `distr`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// use rand::{thread_rng, Sample};
+    /// use rand::distributions::{Alphanumeric, HexDigit};
+    ///
+    /// let mut rng = thread_rng();
+    /// let password = rng.gen_string(Alphanumeric, 20);
+    /// let token = rng.gen_string(HexDigit, 32);
+    /// ```
+    #[cfg(feature="std")]
+    fn gen_string<D: Distribution<char>>(&mut self, distr: D, len: usize) -> String {
+        let mut s = String::with_capacity(len);
+        for _ in 0..len {
+            s.push(distr.sample(self));
+        }
+        s
+    }
+
+    /// Create an iterator that generates values using the given distribution.
+    /// 
+    /// Unlike [`iter`](#method.iter), this returns a real `Iterator`, which
+    /// borrows the generator and can be used with all the usual adapters.
+    /// 
+    /// ### Example
+    /// 
+    /// ```rust
+    /// use rand::{thread_rng, Sample};
+    /// use rand::distributions::{Uniform01, Range, AsciiWordChar};
+    /// 
+    /// let mut rng = thread_rng();
+    /// 
+    /// // Vec of 16 x f32:
+    /// let v: Vec<f32> = rng.sample_iter(Uniform01).take(16).collect();
+    /// 
+    /// // String:
+    /// let s: String = rng.sample_iter(AsciiWordChar).take(7).collect();
+    /// 
+    /// // Dice-rolling:
+    /// let die_range = Range::new(1, 7);
+    /// let mut roll_die = rng.sample_iter(die_range);
+    /// while roll_die.next().unwrap() != 6 {
+    ///     println!("Not a 6; rolling again!");
+    /// }
+    /// ```
+    fn sample_iter<'a, T, D: Distribution<T>>(&'a mut self, distr: D)
+        -> distributions::DistIter<'a, D, Self, T>
+    {
+        distributions::DistIter::new(distr, self)
+    }
+    
+    /// Fill `dest` entirely with values sampled using the [`Default`]
+    /// distribution.
+    /// 
+    /// Slices of integers are filled with a single call to `fill_bytes`, which
+    /// is much faster than sampling each value separately. To fill a slice
+    /// using another distribution, use [`Distribution::fill`].
+    /// 
+    /// ### Example
+    /// 
+    /// ```rust
+    /// use rand::{thread_rng, Sample};
+    /// 
+    /// let mut rng = thread_rng();
+    /// let mut v = vec![0u32; 1000];
+    /// rng.fill(&mut v[..]);
+    /// 
+    /// let mut a = [0.0f64; 16];
+    /// rng.fill(&mut a);
+    /// ```
+    /// 
+    /// [`Distribution::fill`]: distributions/trait.Distribution.html#method.fill
+    fn fill<T>(&mut self, dest: &mut [T]) where Default: Distribution<T> {
+        Default.fill(self, dest)
+    }
+    
+    /// Fill `dest` entirely with random data, using a single call to
+    /// `try_fill`, and report any error of the generator.
+    /// 
+    /// This supports slices and fixed-size arrays of integer types; see
+    /// [`AsByteSliceMut`]. Values are converted from little-endian byte order,
+    /// so that results are reproducible across platforms.
+    /// 
+    /// ### Example
+    /// 
+    /// ```rust
+    /// use rand::{thread_rng, Sample};
+    /// 
+    /// let mut rng = thread_rng();
+    /// let mut key = [0u32; 8];
+    /// rng.try_fill_slice(&mut key).unwrap();
+    /// 
+    /// let mut v = vec![0i16; 100];
+    /// rng.try_fill_slice(&mut v[..]).unwrap();
+    /// ```
+    /// 
+    /// [`AsByteSliceMut`]: trait.AsByteSliceMut.html
+    fn try_fill_slice<T: AsByteSliceMut+?Sized>(&mut self, dest: &mut T)
+        -> Result<(), Error>
+    {
+        self.try_fill(dest.as_byte_slice_mut())?;
+        dest.to_le();
+        Ok(())
     }
     
     /// Construct an iterator on an `Rng`.
     /// 
+    /// For sampling from a distribution, prefer
+    /// [`sample_iter`](#method.sample_iter).
+    /// 
     /// ### Example
     /// 
     /// ```rust
@@ -395,6 +521,92 @@ impl<R: Rng+?Sized> Sample for R {
     }
 }
 
+/// Trait for viewing slices and arrays of integers as a slice of bytes.
+/// 
+/// This is used to fill such buffers with a single call to `fill_bytes` or
+/// `try_fill`; see [`Sample::try_fill_slice`]. It is implemented for slices of
+/// all primitive integer types, and for arrays of these with lengths up to 32
+/// and powers of two up to 4096.
+/// 
+/// [`Sample::try_fill_slice`]: trait.Sample.html#method.try_fill_slice
+pub trait AsByteSliceMut {
+    /// Return a mutable reference to self as a byte slice.
+    fn as_byte_slice_mut(&mut self) -> &mut [u8];
+    
+    /// Convert each element from little-endian to native byte order, i.e.
+    /// byte-swap each element on big-endian platforms and do nothing on
+    /// little-endian ones.
+    fn to_le(&mut self);
+}
+
+impl AsByteSliceMut for [u8] {
+    fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+        self
+    }
+    
+    fn to_le(&mut self) {}
+}
+
+macro_rules! impl_as_byte_slice {
+    ($t:ty) => {
+        impl AsByteSliceMut for [$t] {
+            fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+                unsafe {
+                    slice::from_raw_parts_mut(self.as_mut_ptr() as *mut u8,
+                        self.len() * mem::size_of::<$t>())
+                }
+            }
+            
+            fn to_le(&mut self) {
+                for x in self {
+                    *x = x.to_le();
+                }
+            }
+        }
+    }
+}
+
+impl_as_byte_slice!(u16);
+impl_as_byte_slice!(u32);
+impl_as_byte_slice!(u64);
+#[cfg(feature = "i128_support")] impl_as_byte_slice!(u128);
+impl_as_byte_slice!(usize);
+impl_as_byte_slice!(i8);
+impl_as_byte_slice!(i16);
+impl_as_byte_slice!(i32);
+impl_as_byte_slice!(i64);
+#[cfg(feature = "i128_support")] impl_as_byte_slice!(i128);
+impl_as_byte_slice!(isize);
+
+macro_rules! impl_as_byte_slice_arrays {
+    ($n:expr,) => {
+        impl_as_byte_slice_arrays!(@impl $n);
+    };
+    ($n:expr, $N:ident, $($NN:ident,)*) => {
+        impl_as_byte_slice_arrays!($n - 1, $($NN,)*);
+        impl_as_byte_slice_arrays!(@impl $n);
+    };
+    (!div $n:expr,) => {};
+    (!div $n:expr, $N:ident, $($NN:ident,)*) => {
+        impl_as_byte_slice_arrays!(!div $n / 2, $($NN,)*);
+        impl_as_byte_slice_arrays!(@impl $n);
+    };
+    (@impl $n:expr) => {
+        impl<T> AsByteSliceMut for [T; $n] where [T]: AsByteSliceMut {
+            fn as_byte_slice_mut(&mut self) -> &mut [u8] {
+                self[..].as_byte_slice_mut()
+            }
+            
+            fn to_le(&mut self) {
+                self[..].to_le()
+            }
+        }
+    };
+}
+impl_as_byte_slice_arrays!(32, N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,
+                               N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,N,);
+impl_as_byte_slice_arrays!(!div 4096, N,N,N,N,N,N,N,);
+
 /// The standard RNG. This is designed to be efficient on the current
 /// platform.
 /// 
@@ -434,10 +646,11 @@ impl SeedFromRng for StdRng {
 
 #[cfg(test)]
 mod test {
-    use {Rng, thread_rng, Sample, Error};
-    use mock::MockAddRng;
+    use {Rng, thread_rng, Sample, Error, ErrorKind};
+    use counting::CountingRng;
+    use mock::{MockAddRng, StepRng};
     use distributions::{uniform};
-    use distributions::{Uniform, Range, Exp};
+    use distributions::{Distribution, Uniform, Range, Exp};
     use sequences::Shuffle;
     use std::iter::repeat;
 
@@ -512,7 +725,7 @@ mod test {
         v.shuffle(&mut r);
         let b: &[_] = &[1, 1, 1];
         assert_eq!(v, b);
-        assert_eq!(r.gen_range(0, 1), 0);
+        assert_eq!(r.gen_range(0..1), 0);
     }
 
     #[test]
@@ -526,7 +739,7 @@ mod test {
             v[..].shuffle(r);
             let b: &[_] = &[1, 1, 1];
             assert_eq!(v, b);
-            assert_eq!(r.gen_range(0, 1), 0);
+            assert_eq!(r.gen_range(0..1), 0);
         }
         {
             let mut r = Box::new(rng) as Box<Rng>;
@@ -536,10 +749,82 @@ mod test {
             v[..].shuffle(&mut *r);
             let b: &[_] = &[1, 1, 1];
             assert_eq!(v, b);
-            assert_eq!(r.gen_range(0, 1), 0);
+            assert_eq!(r.gen_range(0..1), 0);
         }
     }
 
+    #[test]
+    fn test_sample_iter() {
+        let mut rng = StepRng::new(0, 1);
+        let v: Vec<u64> = rng.sample_iter(Uniform).skip(1).step_by(2)
+            .take(3).collect();
+        assert_eq!(v, [1, 3, 5]);
+        // the iterator only borrows the generator
+        assert_eq!(rng.next_u64(), 6);
+
+        let r: &mut dyn Rng = &mut thread_rng();
+        let n = r.sample_iter(Range::new(0, 10)).take(100)
+            .filter(|&x| x < 0 || x >= 10).count();
+        assert_eq!(n, 0);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut rng = StepRng::new(0x0807_0605_0403_0201, 0x0808_0808_0808_0808);
+        let mut bytes = [0u8; 9];
+        rng.fill(&mut bytes);
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let mut rng = StepRng::new(0x0807_0605_0403_0201, 0x0808_0808_0808_0808);
+        let mut v = vec![0u32; 3];
+        rng.fill(&mut v[..]);
+        assert_eq!(v, [0x0403_0201, 0x0807_0605, 0x0c0b_0a09]);
+
+        let mut rng = thread_rng();
+        let mut floats = [2.0f64; 100];
+        rng.fill(&mut floats);
+        assert!(floats.iter().all(|&x| x >= 0.0 && x < 1.0));
+
+        let mut words = [0u32; 4];
+        Range::new(10u32, 20).fill(&mut rng, &mut words);
+        assert!(words.iter().all(|&x| x >= 10 && x < 20));
+
+        // through a trait object, without the `fill_bytes` fast path
+        let mut chars = ['x'; 10];
+        (&mut rng as &mut dyn Rng).fill(&mut chars);
+        assert!(chars.iter().any(|&c| c != chars[0]), "{:?}", chars);
+    }
+
+    #[test]
+    fn test_try_fill_slice() {
+        let mut rng = StepRng::new(0x0807_0605_0403_0201, 0x0808_0808_0808_0808);
+        let mut a = [0u16; 5];
+        rng.try_fill_slice(&mut a).unwrap();
+        assert_eq!(a, [0x0201, 0x0403, 0x0605, 0x0807, 0x0a09]);
+
+        let mut rng = StepRng::new(0x0807_0605_0403_0201, 0x0808_0808_0808_0808);
+        let mut v = vec![0i64; 2];
+        rng.try_fill_slice(&mut v[..]).unwrap();
+        assert_eq!(v, [0x0807_0605_0403_0201, 0x100f_0e0d_0c0b_0a09]);
+
+        // a single call to try_fill, however large the array
+        let mut rng = CountingRng::new(thread_rng());
+        rng.try_fill_slice(&mut [0u32; 1024]).unwrap();
+        rng.try_fill_slice(&mut [0u8; 0]).unwrap();
+        assert_eq!(rng.counts().try_fill, 2);
+        assert_eq!(rng.counts().try_fill_len, 4096);
+        assert_eq!(rng.counts().calls(), 2);
+    }
+
+    #[test]
+    fn test_try_fill_slice_error() {
+        let data = [1u8, 2, 3, 4, 5, 6];
+        let mut rng = ::ReadRng::new(&data[..]);
+        let mut a = [0u32; 2];
+        assert_eq!(rng.try_fill_slice(&mut a).unwrap_err().kind,
+                   ErrorKind::Unavailable);
+    }
+
     #[test]
     fn test_sample_from_rng() {
         // use a static Rng type:
@@ -9,11 +9,18 @@
 // except according to those terms.
 
 //! Mock RNG implementations
-//! 
-//! TODO: should this be behind a feature flag? Problem is that Cargo won't
-//! simply use a dependency with a feature flag for tests and without for normal
-//! build (i.e. `rand` couldn't use the mock feature only for tests).
-//! Instead maybe this should be yet another crate? Or just leave it here?
+//!
+//! These generators are only useful for testing, and are only built with the
+//! `mock` feature (or when testing `rand` itself).
+//!
+//! `StepRng`, `ConstRng` and `SeqRng` produce a stream of `u64` words:
+//! `next_u64` returns the next word, `next_u32` returns the low 32 bits of the
+//! next word (discarding the rest), and `fill_bytes` consumes one word per 8
+//! bytes (or part thereof), copying the bytes in little-endian order.
+//! `ByteSeqRng` instead produces a stream of bytes: `next_u32` and `next_u64`
+//! consume 4 and 8 bytes respectively (little-endian), and `fill_bytes`
+//! consumes exactly as many bytes as requested. These rules hold however calls
+//! are interleaved.
 
 use core::num::Wrapping as w;
 use {Rng, SeedableRng, Error};
@@ -21,11 +28,11 @@ use rand_core::impls;
 
 /// A simple implementation of `Rng`, purely for testing.
 /// Returns an arithmetic sequence (i.e. adds a constant each step).
-/// 
+///
 /// ```rust
 /// use rand::Rng;
 /// use rand::mock::MockAddRng;
-/// 
+///
 /// let mut my_rng = MockAddRng::new(2u32, 1u32);
 /// assert_eq!(my_rng.next_u32(), 2u32);
 /// assert_eq!(my_rng.next_u64(), 3u64 + (4u64 << 32));
@@ -57,7 +64,7 @@ impl Rng for MockAddRng<u32> {
     fn next_u128(&mut self) -> u128 {
         impls::next_u128_via_u64(self)
     }
-    
+
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         impls::fill_bytes_via_u32(self, dest);
     }
@@ -80,7 +87,7 @@ impl Rng for MockAddRng<u64> {
     fn next_u128(&mut self) -> u128 {
         impls::next_u128_via_u64(self)
     }
-    
+
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         impls::fill_bytes_via_u64(self, dest);
     }
@@ -98,3 +105,337 @@ impl<T> SeedableRng<T> for MockAddRng<T> where
         MockAddRng::new(seed, 1.into())
     }
 }
+
+// Implement `Rng` for a generator of `u64` words, given `next_u64`.
+macro_rules! impl_rng_via_u64 {
+    ($ty:ty) => {
+        impl Rng for $ty {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.next_word()
+            }
+            #[cfg(feature = "i128_support")]
+            fn next_u128(&mut self) -> u128 {
+                impls::next_u128_via_u64(self)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_u64(self, dest);
+            }
+
+            fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A mock generator yielding an arithmetic sequence of `u64` words.
+///
+/// Each word is the previous one plus `increment` (with wrapping). See the
+/// [module documentation](index.html) for how words are used by each method.
+///
+/// ```rust
+/// use rand::Rng;
+/// use rand::mock::StepRng;
+///
+/// let mut rng = StepRng::new(10, 5);
+/// assert_eq!(rng.next_u64(), 10);
+/// assert_eq!(rng.next_u32(), 15);
+/// let mut buf = [0u8; 3];
+/// rng.fill_bytes(&mut buf);
+/// assert_eq!(buf, [20, 0, 0]);
+/// assert_eq!(rng.next_u64(), 25);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StepRng {
+    v: w<u64>,
+    a: w<u64>,
+}
+
+impl StepRng {
+    /// Create a `StepRng`, yielding an arithmetic sequence starting with
+    /// `initial` and incremented by `increment` each time.
+    pub fn new(initial: u64, increment: u64) -> StepRng {
+        StepRng { v: w(initial), a: w(increment) }
+    }
+
+    fn next_word(&mut self) -> u64 {
+        let result = self.v.0;
+        self.v += self.a;
+        result
+    }
+}
+
+impl_rng_via_u64!(StepRng);
+
+/// A mock generator yielding the same `u64` word every time.
+///
+/// See the [module documentation](index.html) for how the word is used by each
+/// method.
+///
+/// ```rust
+/// use rand::Rng;
+/// use rand::mock::ConstRng;
+///
+/// let mut rng = ConstRng::new(0x0102_0304_0506_0708);
+/// assert_eq!(rng.next_u32(), 0x0506_0708);
+/// assert_eq!(rng.next_u64(), 0x0102_0304_0506_0708);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConstRng {
+    v: u64,
+}
+
+impl ConstRng {
+    /// Create a `ConstRng`, always yielding `value`.
+    pub fn new(value: u64) -> ConstRng {
+        ConstRng { v: value }
+    }
+
+    fn next_word(&mut self) -> u64 {
+        self.v
+    }
+}
+
+impl_rng_via_u64!(ConstRng);
+
+/// A mock generator yielding a scripted sequence of `u64` words.
+///
+/// By default the generator panics once the sequence is exhausted; use
+/// `SeqRng::cycle` to repeat the sequence instead. See the
+/// [module documentation](index.html) for how words are used by each method.
+///
+/// ```rust
+/// use rand::{Rng, Sample};
+/// use rand::mock::SeqRng;
+///
+/// let mut rng = SeqRng::cycle(vec![1, 2]);
+/// assert_eq!(rng.next_u64(), 1);
+/// assert_eq!(rng.next_u32(), 2);
+/// assert_eq!(rng.next_u64(), 1);
+///
+/// // drive exact values through a distribution (`gen_range` uses the
+/// // high word of the word times the size of the range):
+/// let mut rng = SeqRng::new(vec![1, std::u64::MAX]);
+/// assert_eq!(rng.gen_range(0u64..5), 0);
+/// assert_eq!(rng.gen_range(0u64..5), 4);
+/// ```
+#[cfg(feature="std")]
+#[derive(Clone, Debug)]
+pub struct SeqRng {
+    values: Vec<u64>,
+    index: usize,
+    cycle: bool,
+}
+
+#[cfg(feature="std")]
+impl SeqRng {
+    /// Create a `SeqRng`, yielding each of `values` once. Once all values are
+    /// used, any further output causes a panic.
+    pub fn new(values: Vec<u64>) -> SeqRng {
+        SeqRng { values, index: 0, cycle: false }
+    }
+
+    /// Create a `SeqRng`, yielding `values` in a repeating cycle.
+    ///
+    /// Panics if `values` is empty.
+    pub fn cycle(values: Vec<u64>) -> SeqRng {
+        assert!(!values.is_empty(), "SeqRng::cycle called with no values");
+        SeqRng { values, index: 0, cycle: true }
+    }
+
+    fn next_word(&mut self) -> u64 {
+        if self.cycle && self.index == self.values.len() {
+            self.index = 0;
+        }
+        let result = match self.values.get(self.index) {
+            Some(&x) => x,
+            None => panic!("SeqRng: sequence of {} values exhausted",
+                           self.values.len()),
+        };
+        self.index += 1;
+        result
+    }
+}
+
+#[cfg(feature="std")]
+impl_rng_via_u64!(SeqRng);
+
+/// A mock generator yielding a scripted sequence of bytes.
+///
+/// By default the generator panics once the sequence is exhausted; use
+/// `ByteSeqRng::cycle` to repeat the sequence instead. See the
+/// [module documentation](index.html) for how bytes are used by each method.
+///
+/// ```rust
+/// use rand::Rng;
+/// use rand::mock::ByteSeqRng;
+///
+/// let mut rng = ByteSeqRng::new(vec![1, 0, 0, 0, 2, 3, 4]);
+/// assert_eq!(rng.next_u32(), 1);
+/// let mut buf = [0u8; 3];
+/// rng.fill_bytes(&mut buf);
+/// assert_eq!(buf, [2, 3, 4]);
+/// ```
+#[cfg(feature="std")]
+#[derive(Clone, Debug)]
+pub struct ByteSeqRng {
+    bytes: Vec<u8>,
+    index: usize,
+    cycle: bool,
+}
+
+#[cfg(feature="std")]
+impl ByteSeqRng {
+    /// Create a `ByteSeqRng`, yielding each of `bytes` once. Once all bytes
+    /// are used, any further output causes a panic.
+    pub fn new(bytes: Vec<u8>) -> ByteSeqRng {
+        ByteSeqRng { bytes, index: 0, cycle: false }
+    }
+
+    /// Create a `ByteSeqRng`, yielding `bytes` in a repeating cycle.
+    ///
+    /// Panics if `bytes` is empty.
+    pub fn cycle(bytes: Vec<u8>) -> ByteSeqRng {
+        assert!(!bytes.is_empty(), "ByteSeqRng::cycle called with no bytes");
+        ByteSeqRng { bytes, index: 0, cycle: true }
+    }
+}
+
+#[cfg(feature="std")]
+impl Rng for ByteSeqRng {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+    #[cfg(feature = "i128_support")]
+    fn next_u128(&mut self) -> u128 {
+        impls::next_u128_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.cycle && self.index == self.bytes.len() {
+                self.index = 0;
+            }
+            let available = &self.bytes[self.index..];
+            if available.is_empty() {
+                panic!("ByteSeqRng: sequence of {} bytes exhausted",
+                       self.bytes.len());
+            }
+            let n = ::core::cmp::min(available.len(), dest.len() - filled);
+            dest[filled..filled + n].copy_from_slice(&available[..n]);
+            filled += n;
+            self.index += n;
+        }
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {Rng, Sample};
+    use distributions::{Distribution, Range, Normal};
+    use sequences::{Weighted, WeightedChoice};
+    use super::{StepRng, ConstRng, SeqRng, ByteSeqRng};
+
+    #[test]
+    fn test_step_rng_interleaving() {
+        let mut rng = StepRng::new(0x1_0000_0001, 0x1_0000_0001);
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.next_u64(), 0x2_0000_0002);
+        let mut buf = [0u8; 12];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [3, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0]);
+        assert_eq!(rng.next_u64(), 0x5_0000_0005);
+    }
+
+    #[test]
+    fn test_const_rng() {
+        let mut rng = ConstRng::new(0x0102_0304_0506_0708);
+        let mut buf = [0u8; 10];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [8, 7, 6, 5, 4, 3, 2, 1, 8, 7]);
+        assert_eq!(rng.next_u32(), 0x0506_0708);
+        assert_eq!(rng.next_u64(), 0x0102_0304_0506_0708);
+    }
+
+    #[test]
+    fn test_seq_rng_interleaving() {
+        let mut rng = SeqRng::new(vec![1, 2, 0x0403, 4]);
+        assert_eq!(rng.next_u64(), 1);
+        assert_eq!(rng.next_u32(), 2);
+        let mut buf = [0u8; 2];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [3, 4]);
+        assert_eq!(rng.next_u64(), 4);
+    }
+
+    #[test]
+    fn test_seq_rng_cycle() {
+        let mut rng = SeqRng::cycle(vec![5, 6, 7]);
+        let v: Vec<u64> = (0..7).map(|_| rng.next_u64()).collect();
+        assert_eq!(v, [5, 6, 7, 5, 6, 7, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_seq_rng_exhausted() {
+        let mut rng = SeqRng::new(vec![1]);
+        rng.next_u32();
+        rng.next_u32();
+    }
+
+    #[test]
+    fn test_byte_seq_rng_interleaving() {
+        let mut rng = ByteSeqRng::cycle(vec![1, 2, 3]);
+        assert_eq!(rng.next_u32(), 0x0103_0201);
+        let mut buf = [0u8; 4];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [2, 3, 1, 2]);
+        assert_eq!(rng.next_u64(), 0x0103_0201_0302_0103);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_byte_seq_rng_exhausted() {
+        let mut rng = ByteSeqRng::new(vec![1, 2, 3]);
+        rng.next_u32();
+    }
+
+    #[test]
+    fn test_drive_distributions() {
+        // Range samples the high word of `v * 10`; multiples of
+        // `ceil(2^64 / 10)` select each value
+        let step = 0x1999_9999_9999_999a;
+        let mut rng = SeqRng::new(vec![3 * step, 7 * step, 2 * step]);
+        let range = Range::new(0u64, 10);
+        let v: Vec<u64> = (0..3).map(|_| rng.sample(range)).collect();
+        assert_eq!(v, [3, 7, 2]);
+
+        // a constant generator yields the same normal sample every time
+        let normal = Normal::new(2.0, 3.0);
+        let mut rng = ConstRng::new(0x1234_5678_9abc_def0);
+        let x = normal.sample(&mut rng);
+        assert_eq!(normal.sample(&mut rng), x);
+
+        // WeightedChoice samples a u32 weight in [0, 3)
+        let wc = WeightedChoice::new(vec![Weighted { weight: 1, item: 'a' },
+                                          Weighted { weight: 2, item: 'b' }]);
+        let step = 0x5555_5556;
+        let mut rng = SeqRng::new(vec![0, step, 2 * step, 0]);
+        let v: Vec<char> = (0..4).map(|_| wc.sample(&mut rng)).collect();
+        assert_eq!(v, ['a', 'b', 'b', 'a']);
+    }
+}
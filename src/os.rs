@@ -14,7 +14,7 @@
 use std::{mem, fmt};
 use std::io::Read;
 
-use {Rng, Error};
+use {Rng, CryptoRng, Error};
 // TODO: replace many of the panics below with Result error handling
 
 /// A random number generator that retrieves randomness straight from
@@ -76,6 +76,8 @@ impl Rng for OsRng {
     }
 }
 
+impl CryptoRng for OsRng {}
+
 // Specialisation of `ReadRng` for our purposes
 #[derive(Debug)]
 struct ReadRng<R> (R);
@@ -0,0 +1,573 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Wrappers to record the output of an RNG and replay it later.
+//!
+//! `RecordingRng` passes through all output of the wrapped generator while
+//! writing a log of every call to a `Write` sink. `ReplayRng` reads such a log
+//! back and yields exactly the same values, so long as it is asked for output
+//! in the same sequence of calls. This allows the randomness consumed by some
+//! piece of code to be captured (e.g. while reproducing a bug) and replayed in
+//! a test.
+//!
+//! The log is a compact binary format: each call is written as a one-byte tag
+//! followed by the output in little-endian byte order. For `fill_bytes` and
+//! `try_fill` the tag is followed by the length of the buffer as a `u64`, then
+//! the bytes themselves. `fill_bytes` and `try_fill` use the same tag and may
+//! replay each other; if `try_fill` fails, nothing is recorded.
+
+use std::error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use {Rng, Error, ErrorKind};
+
+const TAG_U32: u8 = 1;
+const TAG_U64: u8 = 2;
+#[cfg(feature = "i128_support")]
+const TAG_U128: u8 = 3;
+const TAG_FILL: u8 = 4;
+
+/// A single call on an `Rng`, as written to the log by `RecordingRng`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Call {
+    /// A call to `next_u32`
+    NextU32,
+    /// A call to `next_u64`
+    NextU64,
+    /// A call to `next_u128`
+    #[cfg(feature = "i128_support")]
+    NextU128,
+    /// A call to `fill_bytes` or `try_fill` with a buffer of the given length
+    Fill(usize),
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Call::NextU32 => write!(f, "next_u32"),
+            Call::NextU64 => write!(f, "next_u64"),
+            #[cfg(feature = "i128_support")]
+            Call::NextU128 => write!(f, "next_u128"),
+            Call::Fill(len) => write!(f, "fill of {} bytes", len),
+        }
+    }
+}
+
+/// A wrapper around an RNG which writes all output to a log.
+///
+/// All output of the wrapped RNG is passed through unchanged. The log can be
+/// read back with `ReplayRng`; see the [module documentation](index.html) for
+/// the format.
+///
+/// # Panics
+///
+/// Only the `try_fill` method will report errors from the sink. All other
+/// methods will panic if writing to the log fails.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::{Rng, NewSeeded};
+/// use rand::prng::XorShiftRng;
+/// use rand::recording::{RecordingRng, ReplayRng};
+///
+/// let mut rng = RecordingRng::new(XorShiftRng::new().unwrap(), Vec::new());
+/// let x = rng.next_u32();
+/// let y = rng.next_u64();
+///
+/// let (_, log) = rng.into_inner();
+/// let mut replay = ReplayRng::new(&log[..]);
+/// assert_eq!(replay.next_u32(), x);
+/// assert_eq!(replay.next_u64(), y);
+/// ```
+#[derive(Debug)]
+pub struct RecordingRng<R, W> {
+    rng: R,
+    sink: W,
+}
+
+impl<R: Rng, W: Write> RecordingRng<R, W> {
+    /// Create a new `RecordingRng`, passing through the output of `rng` and
+    /// writing a log of it to `sink`.
+    pub fn new(rng: R, sink: W) -> RecordingRng<R, W> {
+        RecordingRng { rng, sink }
+    }
+
+    /// Consume self, returning the wrapped RNG and the log sink.
+    pub fn into_inner(self) -> (R, W) {
+        (self.rng, self.sink)
+    }
+
+    fn record(&mut self, tag: u8, bytes: &[u8]) {
+        if let Err(e) = self.write_record(tag, bytes) {
+            panic!("RecordingRng: failed to write log: {}", e);
+        }
+    }
+
+    fn write_record(&mut self, tag: u8, bytes: &[u8]) -> io::Result<()> {
+        self.sink.write_all(&[tag])?;
+        self.sink.write_all(bytes)
+    }
+
+    fn write_fill(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.write_record(TAG_FILL, &(bytes.len() as u64).to_le_bytes())?;
+        self.sink.write_all(bytes)
+    }
+}
+
+impl<R: Rng, W: Write> Rng for RecordingRng<R, W> {
+    fn next_u32(&mut self) -> u32 {
+        let x = self.rng.next_u32();
+        self.record(TAG_U32, &x.to_le_bytes());
+        x
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let x = self.rng.next_u64();
+        self.record(TAG_U64, &x.to_le_bytes());
+        x
+    }
+
+    #[cfg(feature = "i128_support")]
+    fn next_u128(&mut self) -> u128 {
+        let x = self.rng.next_u128();
+        self.record(TAG_U128, &x.to_le_bytes());
+        x
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+        if let Err(e) = self.write_fill(dest) {
+            panic!("RecordingRng: failed to write log: {}", e);
+        }
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill(dest)?;
+        self.write_fill(dest)
+            .map_err(|e| Error::new(ErrorKind::Other, Some(Box::new(e))))
+    }
+}
+
+/// An error reported when replaying a log with `ReplayRng`.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The consumer asked for different output than was recorded next.
+    Mismatch {
+        /// The call found next in the log
+        recorded: Call,
+        /// The call made on the `ReplayRng`
+        requested: Call,
+    },
+    /// The end of the log was reached, after the last complete record.
+    Exhausted,
+    /// The log ends in the middle of a record; it is corrupt.
+    Truncated,
+    /// The log contains an unknown tag byte; it is corrupt or was not written
+    /// by `RecordingRng`.
+    InvalidTag(u8),
+    /// Reading the log failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReplayError::Mismatch { recorded, requested } =>
+                write!(f, "requested {} but the log recorded {}",
+                       requested, recorded),
+            ReplayError::Exhausted => write!(f, "end of the log reached"),
+            ReplayError::Truncated =>
+                write!(f, "the log ends in the middle of a record"),
+            ReplayError::InvalidTag(tag) =>
+                write!(f, "invalid tag {:#x} in the log", tag),
+            ReplayError::Io(ref e) => write!(f, "failed to read log: {}", e),
+        }
+    }
+}
+
+impl error::Error for ReplayError {
+    fn description(&self) -> &str {
+        match *self {
+            ReplayError::Mismatch { .. } => "call sequence differs from log",
+            ReplayError::Exhausted => "end of log reached",
+            ReplayError::Truncated => "log ends in the middle of a record",
+            ReplayError::InvalidTag(_) => "invalid tag in log",
+            ReplayError::Io(_) => "failed to read log",
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            ReplayError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> ReplayError {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => ReplayError::Truncated,
+            _ => ReplayError::Io(e),
+        }
+    }
+}
+
+/// An RNG replaying a log written by `RecordingRng`.
+///
+/// The sequence of calls made on this RNG must match the recorded sequence
+/// exactly; e.g. if the log starts with a `next_u64`, calling `next_u32` first
+/// is an error. The `try_*` methods report a `ReplayError` when this happens.
+///
+/// After a `Mismatch` the recorded call is kept, so every later call fails
+/// the same way until the recorded call is requested. An invalid tag, a
+/// truncated record or a failure to read the log is permanent: all later calls return the same
+/// error (for `Io`, an error of the same kind).
+///
+/// # Panics
+///
+/// Only the `try_*` methods will report errors. All other methods will panic
+/// if the requested call differs from the log, the log is exhausted or
+/// reading it fails.
+#[derive(Debug)]
+pub struct ReplayRng<R> {
+    reader: R,
+    // A call read from the log which did not match the requested one, and
+    // whose output has not been read yet.
+    pending: Option<Call>,
+    // Set once the log turned out to be corrupt or unreadable.
+    failed: Option<Failure>,
+}
+
+/// A permanent `ReplayError`, which unlike the error itself can be copied.
+#[derive(Clone, Copy, Debug)]
+enum Failure {
+    InvalidTag(u8),
+    Truncated,
+    Io(io::ErrorKind),
+}
+
+impl Failure {
+    fn from_error(e: &ReplayError) -> Option<Failure> {
+        match *e {
+            ReplayError::InvalidTag(tag) => Some(Failure::InvalidTag(tag)),
+            ReplayError::Truncated => Some(Failure::Truncated),
+            ReplayError::Io(ref e) => Some(Failure::Io(e.kind())),
+            ReplayError::Mismatch { .. } | ReplayError::Exhausted => None,
+        }
+    }
+
+    fn to_error(self) -> ReplayError {
+        match self {
+            Failure::InvalidTag(tag) => ReplayError::InvalidTag(tag),
+            Failure::Truncated => ReplayError::Truncated,
+            Failure::Io(kind) => ReplayError::Io(io::Error::from(kind)),
+        }
+    }
+}
+
+impl<R: Read> ReplayRng<R> {
+    /// Create a new `ReplayRng`, reading a log from `reader`.
+    pub fn new(reader: R) -> ReplayRng<R> {
+        ReplayRng { reader, pending: None, failed: None }
+    }
+
+    /// Return the next `u32` from the log.
+    pub fn try_next_u32(&mut self) -> Result<u32, ReplayError> {
+        let mut buf = [0u8; 4];
+        self.replay(Call::NextU32, &mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    /// Return the next `u64` from the log.
+    pub fn try_next_u64(&mut self) -> Result<u64, ReplayError> {
+        let mut buf = [0u8; 8];
+        self.replay(Call::NextU64, &mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// Return the next `u128` from the log.
+    #[cfg(feature = "i128_support")]
+    pub fn try_next_u128(&mut self) -> Result<u128, ReplayError> {
+        let mut buf = [0u8; 16];
+        self.replay(Call::NextU128, &mut buf)?;
+        Ok(u128::from_le_bytes(buf))
+    }
+
+    /// Fill `dest` with the next recorded bytes. The recorded buffer must
+    /// have the same length as `dest`.
+    pub fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ReplayError> {
+        self.replay(Call::Fill(dest.len()), dest)
+    }
+
+    /// Read the output of the next record into `dest`, if the record is a
+    /// `requested` call.
+    fn replay(&mut self, requested: Call, dest: &mut [u8]) -> Result<(), ReplayError> {
+        if let Some(failure) = self.failed {
+            return Err(failure.to_error());
+        }
+        let result = self.read_record(requested, dest);
+        if let Err(ref e) = result {
+            self.failed = Failure::from_error(e);
+        }
+        result
+    }
+
+    fn read_record(&mut self, requested: Call, dest: &mut [u8]) -> Result<(), ReplayError> {
+        let recorded = match self.pending.take() {
+            Some(call) => call,
+            None => self.read_call()?,
+        };
+        if recorded != requested {
+            self.pending = Some(recorded);
+            return Err(ReplayError::Mismatch { recorded, requested });
+        }
+        self.reader.read_exact(dest)?;
+        Ok(())
+    }
+
+    /// Read the tag of the next record, and for fills the length. The log
+    /// may only end before a tag; everywhere else it is truncated.
+    fn read_call(&mut self) -> Result<Call, ReplayError> {
+        let mut tag = [0u8; 1];
+        loop {
+            match self.reader.read(&mut tag) {
+                Ok(0) => return Err(ReplayError::Exhausted),
+                Ok(_) => break,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(ReplayError::Io(e)),
+            }
+        }
+        match tag[0] {
+            TAG_U32 => Ok(Call::NextU32),
+            TAG_U64 => Ok(Call::NextU64),
+            #[cfg(feature = "i128_support")]
+            TAG_U128 => Ok(Call::NextU128),
+            TAG_FILL => {
+                let mut len = [0u8; 8];
+                self.reader.read_exact(&mut len)?;
+                Ok(Call::Fill(u64::from_le_bytes(len) as usize))
+            }
+            tag => Err(ReplayError::InvalidTag(tag)),
+        }
+    }
+}
+
+fn unwrap_replay<T>(result: Result<T, ReplayError>) -> T {
+    match result {
+        Ok(x) => x,
+        Err(e) => panic!("ReplayRng: {}", e),
+    }
+}
+
+impl<R: Read> Rng for ReplayRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        unwrap_replay(self.try_next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        unwrap_replay(self.try_next_u64())
+    }
+
+    #[cfg(feature = "i128_support")]
+    fn next_u128(&mut self) -> u128 {
+        unwrap_replay(self.try_next_u128())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        unwrap_replay(self.try_fill_bytes(dest))
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.try_fill_bytes(dest).map_err(|e| {
+            let kind = match e {
+                ReplayError::Exhausted => ErrorKind::Unavailable,
+                _ => ErrorKind::Other,
+            };
+            Error::new(kind, Some(Box::new(e)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {Rng, Sample, SeedableRng, ErrorKind};
+    use distributions::{Range, Normal};
+    use prng::XorShiftRng;
+    use sequences::Shuffle;
+    use super::{RecordingRng, ReplayRng, ReplayError, Call};
+
+    fn recording() -> RecordingRng<XorShiftRng, Vec<u8>> {
+        let rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        RecordingRng::new(rng, Vec::new())
+    }
+
+    #[test]
+    fn test_record_passes_through() {
+        let mut a = XorShiftRng::from_seed([1, 2, 3, 4]);
+        let mut b = recording();
+        let (mut x, mut y) = ([0u8; 13], [0u8; 13]);
+        assert_eq!(a.next_u32(), b.next_u32());
+        assert_eq!(a.next_u64(), b.next_u64());
+        a.fill_bytes(&mut x);
+        b.fill_bytes(&mut y);
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut rng = recording();
+        let mut bytes = [0u8; 7];
+        let a = rng.next_u32();
+        let b = rng.next_u64();
+        rng.fill_bytes(&mut bytes);
+        let c = rng.next_u32();
+
+        let (_, log) = rng.into_inner();
+        assert_eq!(log.len(), (1 + 4) + (1 + 8) + (1 + 8 + 7) + (1 + 4));
+
+        let mut replay = ReplayRng::new(&log[..]);
+        let mut replayed = [0u8; 7];
+        assert_eq!(replay.next_u32(), a);
+        assert_eq!(replay.next_u64(), b);
+        replay.try_fill(&mut replayed).unwrap();
+        assert_eq!(replayed, bytes);
+        assert_eq!(replay.next_u32(), c);
+        match replay.try_next_u32() {
+            Err(ReplayError::Exhausted) => {}
+            r => panic!("expected exhausted log, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_replay_distributions() {
+        let mut rng = recording();
+        let range = Range::new(-20, 100);
+        let normal = Normal::new(3.0, 2.0);
+        let mut v = [1, 2, 3, 4, 5, 6, 7, 8];
+        let x = rng.sample(range);
+        let y = rng.sample(normal);
+        v.shuffle(&mut rng);
+
+        let (_, log) = rng.into_inner();
+        let mut replay = ReplayRng::new(&log[..]);
+        let mut w = [1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(replay.sample(range), x);
+        assert_eq!(replay.sample(normal), y);
+        w.shuffle(&mut replay);
+        assert_eq!(v, w);
+    }
+
+    #[test]
+    fn test_replay_mismatch() {
+        let mut rng = recording();
+        rng.next_u64();
+        rng.fill_bytes(&mut [0u8; 8]);
+        let (_, log) = rng.into_inner();
+
+        let mut replay = ReplayRng::new(&log[..]);
+        match replay.try_next_u32() {
+            Err(ReplayError::Mismatch { recorded, requested }) => {
+                assert_eq!(recorded, Call::NextU64);
+                assert_eq!(requested, Call::NextU32);
+            }
+            r => panic!("expected mismatch, got {:?}", r),
+        }
+
+        let mut replay = ReplayRng::new(&log[..]);
+        replay.next_u64();
+        let err = replay.try_fill(&mut [0u8; 4]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_replay_truncated() {
+        let mut rng = recording();
+        rng.next_u32();
+        rng.fill_bytes(&mut [0u8; 3]);
+        let (_, log) = rng.into_inner();
+        assert_eq!(log.len(), (1 + 4) + (1 + 8 + 3));
+
+        // inside the payload of the first record
+        match ReplayRng::new(&log[..3]).try_next_u32() {
+            Err(ReplayError::Truncated) => {}
+            r => panic!("expected truncated log, got {:?}", r),
+        }
+        // at the record boundary
+        let mut replay = ReplayRng::new(&log[..5]);
+        replay.next_u32();
+        match replay.try_fill_bytes(&mut [0u8; 3]) {
+            Err(ReplayError::Exhausted) => {}
+            r => panic!("expected exhausted log, got {:?}", r),
+        }
+        // inside the length and the payload of the second record
+        for &len in [7, 14, 16].iter() {
+            let mut replay = ReplayRng::new(&log[..len]);
+            replay.next_u32();
+            for _ in 0..2 {
+                match replay.try_fill_bytes(&mut [0u8; 3]) {
+                    Err(ReplayError::Truncated) => {}
+                    r => panic!("expected truncated log at {}, got {:?}", len, r),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_replay_after_mismatch() {
+        let mut rng = recording();
+        let x = rng.next_u64();
+        let mut bytes = [0u8; 8];
+        rng.fill_bytes(&mut bytes);
+        let (_, log) = rng.into_inner();
+
+        // The mismatched record is not consumed: asking again gives the same
+        // error, and asking for the recorded call succeeds.
+        let mut replay = ReplayRng::new(&log[..]);
+        for _ in 0..2 {
+            match replay.try_next_u32() {
+                Err(ReplayError::Mismatch { recorded: Call::NextU64, .. }) => {}
+                r => panic!("expected mismatch, got {:?}", r),
+            }
+        }
+        assert_eq!(replay.next_u64(), x);
+        for _ in 0..2 {
+            match replay.try_fill_bytes(&mut [0u8; 4]) {
+                Err(ReplayError::Mismatch { recorded: Call::Fill(8), .. }) => {}
+                r => panic!("expected mismatch, got {:?}", r),
+            }
+        }
+        let mut replayed = [0u8; 8];
+        replay.fill_bytes(&mut replayed);
+        assert_eq!(replayed, bytes);
+    }
+
+    #[test]
+    fn test_replay_invalid_tag() {
+        // an invalid tag followed by what looks like a valid record
+        let log = [0xffu8, 1, 0, 0, 0, 0];
+        let mut replay = ReplayRng::new(&log[..]);
+        for _ in 0..3 {
+            match replay.try_next_u32() {
+                Err(ReplayError::InvalidTag(0xff)) => {}
+                r => panic!("expected invalid tag, got {:?}", r),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_replay_exhausted_panics() {
+        let log: [u8; 0] = [];
+        ReplayRng::new(&log[..]).next_u32();
+    }
+}
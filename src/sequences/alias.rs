@@ -0,0 +1,299 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weighted sampling in constant time with the alias method
+
+use Rng;
+use distributions::{Distribution, Uniform01, Rand};
+use distributions::range::{Range, RangeInt};
+
+/// A type that can be used as a weight of `AliasIndex` and `AliasChoice`.
+///
+/// This is implemented for `f32`, `f64` and the unsigned integer types.
+/// Weights are converted to `f64`, so integer weights above 2^53 are
+/// rounded.
+pub trait AliasWeight: Copy {
+    #[doc(hidden)]
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! alias_weight_impl {
+    ($($ty:ty),*) => {
+        $(
+            impl AliasWeight for $ty {
+                #[inline]
+                fn to_f64(self) -> f64 { self as f64 }
+            }
+        )*
+    }
+}
+
+alias_weight_impl! { f32, f64, u8, u16, u32, u64, usize }
+
+/// A distribution that samples indices `0..n` with probabilities
+/// proportional to `n` given weights.
+///
+/// This uses the alias method[1] as described by Vose[2]: the table is
+/// built in `O(n)` time, after which every sample takes constant time and
+/// two random numbers, however many weights there are. Compare this with
+/// `WeightedChoice`, which binary searches on every sample and is limited to
+/// a total weight of `u32::MAX`.
+///
+/// [1]: A. J. Walker (1977). [*An Efficient Method for Generating Discrete
+/// Random Variables with General
+/// Distributions*](https://doi.org/10.1145/355744.355749). ACM Transactions
+/// on Mathematical Software 3, 3, 253-256.
+///
+/// [2]: Michael D. Vose (1991). [*A linear algorithm for generating random
+/// numbers with a given distribution*](https://doi.org/10.1109/32.92917).
+/// IEEE Transactions on Software Engineering 17, 9, 972-975.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::Distribution;
+/// use rand::sequences::AliasIndex;
+///
+/// let index = AliasIndex::new(&[0.5, 2.0, 0.0, 1.5]);
+/// let mut rng = rand::thread_rng();
+/// for _ in 0..16 {
+///     // on average prints 0 twice, 1 8 times and 3 6 times, never 2
+///     println!("{}", index.sample(&mut rng));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AliasIndex {
+    // Sampling picks a column `i` uniformly, and returns `i` with
+    // probability `prob[i]` or otherwise `alias[i]`.
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+    range: Range<RangeInt<usize>>,
+}
+
+parameter_error! {
+    /// An error constructing an `AliasIndex` or `AliasChoice`.
+    pub enum AliasError {
+        /// No weights were given.
+        NoItems => "no weights",
+        /// A weight is negative, NaN or infinite.
+        InvalidWeight => "a weight is negative, NaN or infinite",
+        /// The weights are all zero.
+        ZeroTotalWeight => "the total weight is zero",
+        /// The sum of the weights overflows an `f64`.
+        TotalWeightOverflow => "the total weight is larger than an `f64` can contain",
+        /// The numbers of items and weights differ.
+        LengthMismatch => "the numbers of items and weights differ",
+    }
+}
+
+impl AliasIndex {
+    /// Create a new `AliasIndex` from the weights of the indices
+    /// `0..weights.len()`. Panics if there are no weights, a weight is
+    /// invalid or the total weight is zero or overflows; see `try_new`.
+    pub fn new<W: AliasWeight>(weights: &[W]) -> AliasIndex {
+        AliasIndex::try_new(weights).unwrap_or_else(|e| panic!("AliasIndex::new: {}", e))
+    }
+
+    /// Create a new `AliasIndex`, or return an error if `weights` is empty
+    /// (`NoItems`), a weight is negative, NaN or infinite (`InvalidWeight`),
+    /// all weights are zero (`ZeroTotalWeight`) or their sum is infinite
+    /// (`TotalWeightOverflow`).
+    pub fn try_new<W: AliasWeight>(weights: &[W]) -> Result<AliasIndex, AliasError> {
+        let n = weights.len();
+        if n == 0 {
+            return Err(AliasError::NoItems);
+        }
+        let mut total = 0.0;
+        for w in weights.iter().map(|w| w.to_f64()) {
+            if !(w >= 0.0 && w.is_finite()) {
+                return Err(AliasError::InvalidWeight);
+            }
+            total += w;
+        }
+        if total == 0.0 {
+            return Err(AliasError::ZeroTotalWeight);
+        } else if !total.is_finite() {
+            return Err(AliasError::TotalWeightOverflow);
+        }
+
+        // Scale the weights to an average of one. Each column is then
+        // filled by a weight below one (`small`), topped up from a weight
+        // above one (`large`), whose remainder is put back on the worklist.
+        let mut prob: Vec<f64> = weights.iter()
+            .map(|w| w.to_f64() / total * n as f64)
+            .collect();
+        let mut alias: Vec<usize> = (0..n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| prob[i] < 1.0);
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            alias[s] = l;
+            prob[l] = (prob[l] + prob[s]) - 1.0;
+            if prob[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Without rounding errors the remaining columns would all be exactly
+        // one, so make them so.
+        for i in small.into_iter().chain(large) {
+            prob[i] = 1.0;
+        }
+
+        Ok(AliasIndex { prob, alias, range: Range::new(0, n) })
+    }
+
+    /// The number of indices.
+    pub fn len(&self) -> usize {
+        self.prob.len()
+    }
+
+    /// Always `false`: an `AliasIndex` has at least one index.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl Distribution<usize> for AliasIndex {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> usize {
+        let i = self.range.sample(rng);
+        if f64::rand(rng, Uniform01) < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
+
+/// A distribution that samples items with probabilities proportional to
+/// given weights, using `AliasIndex`.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::Distribution;
+/// use rand::sequences::AliasChoice;
+///
+/// let choice = AliasChoice::new(vec!['a', 'b', 'c'], &[2u32, 4, 1]);
+/// let mut rng = rand::thread_rng();
+/// for _ in 0..16 {
+///     // on average prints 'a' 4 times, 'b' 8 and 'c' twice.
+///     println!("{}", choice.sample(&mut rng));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AliasChoice<T: Clone> {
+    items: Vec<T>,
+    index: AliasIndex,
+}
+
+impl<T: Clone> AliasChoice<T> {
+    /// Create a new `AliasChoice`, where `items[i]` has weight
+    /// `weights[i]`. Panics if the numbers of items and weights differ, or
+    /// in the cases `AliasIndex::new` does; see `try_new`.
+    pub fn new<W: AliasWeight>(items: Vec<T>, weights: &[W]) -> AliasChoice<T> {
+        AliasChoice::try_new(items, weights)
+            .unwrap_or_else(|e| panic!("AliasChoice::new: {}", e))
+    }
+
+    /// Create a new `AliasChoice`, or return `LengthMismatch` if the numbers
+    /// of items and weights differ, or the errors of `AliasIndex::try_new`.
+    pub fn try_new<W: AliasWeight>(items: Vec<T>, weights: &[W])
+        -> Result<AliasChoice<T>, AliasError>
+    {
+        if items.len() != weights.len() {
+            return Err(AliasError::LengthMismatch);
+        }
+        let index = AliasIndex::try_new(weights)?;
+        Ok(AliasChoice { items, index })
+    }
+}
+
+impl<T: Clone> Distribution<T> for AliasChoice<T> {
+    fn sample<R: Rng+?Sized>(&self, rng: &mut R) -> T {
+        self.items[self.index.sample(rng)].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f64, u32, u64};
+    use SeedableRng;
+    use distributions::Distribution;
+    use distributions::test::assert_discrete_fit;
+    use prng::XorShiftRng;
+    use super::{AliasIndex, AliasChoice, AliasError, AliasWeight};
+
+    fn test_fit<W: AliasWeight>(weights: &[W]) {
+        let mut rng = XorShiftRng::from_seed([0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb]);
+        let index = AliasIndex::new(weights);
+        assert_eq!(index.len(), weights.len());
+        let total: f64 = weights.iter().map(|w| w.to_f64()).sum();
+        let samples: Vec<u64> = (0..20_000).map(|_| index.sample(&mut rng) as u64).collect();
+        for &i in samples.iter() {
+            assert!(weights[i as usize].to_f64() > 0.0, "sampled {} with weight zero", i);
+        }
+        assert_discrete_fit(&samples, |k| {
+            weights.get(k as usize).map_or(0.0, |w| w.to_f64() / total)
+        });
+    }
+
+    #[test]
+    fn test_alias_index() {
+        test_fit(&[1u32]);
+        test_fit(&[1u32, 2, 3, 4]);
+        test_fit(&[0u8, 7, 0, 0, 1, 0]);
+        test_fit(&[0.5f64, 2.0, 0.0, 1.5]);
+        test_fit(&[1e-3f32, 1.0, 1e3]);
+        test_fit(&[u64::MAX, u64::MAX / 3, 1 << 62]);
+        test_fit(&[f64::MAX / 4.0, f64::MAX / 2.0]);
+
+        let weights: Vec<usize> = (0..1000).map(|i| (i * 7919) % 1009).collect();
+        test_fit(&weights);
+    }
+
+    #[test]
+    fn test_alias_choice() {
+        let mut rng = ::test::rng();
+        let choice = AliasChoice::new(vec!["a", "b", "c"], &[0.0, 1.0, 0.0]);
+        for _ in 0..100 {
+            assert_eq!(choice.sample(&mut rng), "b");
+        }
+        let choice = AliasChoice::new(vec![10, 20], &[3u16, 1]);
+        let count = (0..4000).filter(|_| choice.sample(&mut rng) == 10).count();
+        assert!(count > 2800 && count < 3200, "{}", count);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_alias_index_no_items() {
+        AliasIndex::new::<f64>(&[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_alias_choice_length_mismatch() {
+        AliasChoice::new(vec![1, 2, 3], &[1u32, 2]);
+    }
+
+    #[test]
+    fn test_alias_try_new() {
+        assert_eq!(AliasIndex::try_new::<u32>(&[]).unwrap_err(), AliasError::NoItems);
+        for &w in [-1.0, f64::NAN, f64::INFINITY].iter() {
+            assert_eq!(AliasIndex::try_new(&[1.0, w]).unwrap_err(), AliasError::InvalidWeight);
+        }
+        assert_eq!(AliasIndex::try_new(&[0u32, 0]).unwrap_err(), AliasError::ZeroTotalWeight);
+        assert_eq!(AliasIndex::try_new(&[f64::MAX, f64::MAX]).unwrap_err(),
+                   AliasError::TotalWeightOverflow);
+        assert_eq!(AliasChoice::try_new(vec![1], &[1u32, 2]).unwrap_err(),
+                   AliasError::LengthMismatch);
+        assert!(AliasChoice::try_new(vec![1, 2], &[u32::MAX, u32::MAX]).is_ok());
+    }
+}
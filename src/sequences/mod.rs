@@ -13,10 +13,14 @@
 use {Rng, Sample};
 
 #[cfg(feature="std")]
-pub use self::weighted::{Weighted, WeightedChoice};
+pub use self::weighted::{Weighted, WeightedChoice, WeightedError};
+#[cfg(feature="std")]
+pub use self::alias::{AliasIndex, AliasChoice, AliasError, AliasWeight};
 
 #[cfg(feature="std")]
 mod weighted;
+#[cfg(feature="std")]
+mod alias;
 
 /// This trait implements a `choose` operations on slices and sequences.
 pub trait Choose<T> {
@@ -43,7 +47,7 @@ impl<'a, T> Choose<&'a T> for &'a [T] {
         if self.is_empty() {
             None
         } else {
-            Some(&self[rng.gen_range(0, self.len())])
+            Some(&self[rng.gen_range(0..self.len())])
         }
     }
 }
@@ -54,7 +58,7 @@ impl<'a, T> Choose<&'a mut T> for &'a mut [T] {
             None
         } else {
             let len = self.len();
-            Some(&mut self[rng.gen_range(0, len)])
+            Some(&mut self[rng.gen_range(0..len)])
         }
     }
 }
@@ -65,7 +69,7 @@ impl<T> Choose<T> for Vec<T> {
         if self.is_empty() {
             None
         } else {
-            let index = rng.gen_range(0, self.len());
+            let index = rng.gen_range(0..self.len());
             self.drain(index..).next()
         }
     }
@@ -94,7 +98,7 @@ pub fn sample<T, I, R>(rng: &mut R, iterable: I, amount: usize) -> Vec<T>
     // continue unless the iterator was exhausted
     if reservoir.len() == amount {
         for (i, elem) in iter.enumerate() {
-            let k = rng.gen_range(0, i + 1 + amount);
+            let k = rng.gen_range(0..i + 1 + amount);
             if let Some(spot) = reservoir.get_mut(k) {
                 *spot = elem;
             }
@@ -133,7 +137,7 @@ impl<'a, T> Shuffle for &'a mut [T] {
             // invariant: elements with index >= i have been locked in place.
             i -= 1;
             // lock element i in place.
-            self.swap(i, rng.gen_range(0, i + 1));
+            self.swap(i, rng.gen_range(0..i + 1));
         }
     }
 }
@@ -31,6 +31,9 @@ pub struct Weighted<T> {
 /// Each item has an associated weight that influences how likely it
 /// is to be chosen: higher weight is more likely.
 ///
+/// Each sample takes a binary search over the items. `AliasChoice` samples
+/// in constant time and accepts larger or fractional weights.
+///
 /// # Example
 ///
 /// ```rust
@@ -53,6 +56,18 @@ pub struct WeightedChoice<T: Clone> {
     weight_range: Range<RangeInt<u32>>,
 }
 
+parameter_error! {
+    /// An error constructing a `WeightedChoice`.
+    pub enum WeightedError {
+        /// No items were given.
+        NoItems => "no items",
+        /// The weights of all items are zero.
+        ZeroTotalWeight => "the total weight is zero",
+        /// The sum of the weights overflows a `u32`.
+        TotalWeightOverflow => "the total weight is larger than a `u32` can contain",
+    }
+}
+
 impl<T: Clone> WeightedChoice<T> {
     /// Create a new `WeightedChoice`.
     ///
@@ -60,9 +75,22 @@ impl<T: Clone> WeightedChoice<T> {
     /// - `v` is empty
     /// - the total weight is 0
     /// - the total weight is larger than a `u32` can contain.
-    pub fn new(mut items: Vec<Weighted<T>>) -> WeightedChoice<T> {
+    ///
+    /// See `try_new` for a non-panicking alternative.
+    pub fn new(items: Vec<Weighted<T>>) -> WeightedChoice<T> {
+        WeightedChoice::try_new(items)
+            .unwrap_or_else(|e| panic!("WeightedChoice::new: {}", e))
+    }
+
+    /// Create a new `WeightedChoice`, or return an error if `items` is
+    /// empty (`NoItems`), the total weight is 0 (`ZeroTotalWeight`), or the
+    /// total weight is larger than a `u32` can contain
+    /// (`TotalWeightOverflow`).
+    pub fn try_new(mut items: Vec<Weighted<T>>) -> Result<WeightedChoice<T>, WeightedError> {
         // strictly speaking, this is subsumed by the total weight == 0 case
-        assert!(!items.is_empty(), "WeightedChoice::new called with no items");
+        if items.is_empty() {
+            return Err(WeightedError::NoItems);
+        }
 
         let mut running_total: u32 = 0;
 
@@ -72,20 +100,21 @@ impl<T: Clone> WeightedChoice<T> {
         for ref mut item in items.iter_mut() {
             running_total = match running_total.checked_add(item.weight) {
                 Some(n) => n,
-                None => panic!("WeightedChoice::new called with a total weight \
-                               larger than a u32 can contain")
+                None => return Err(WeightedError::TotalWeightOverflow),
             };
 
             item.weight = running_total;
         }
-        assert!(running_total != 0, "WeightedChoice::new called with a total weight of 0");
+        if running_total == 0 {
+            return Err(WeightedError::ZeroTotalWeight);
+        }
 
-        WeightedChoice {
+        Ok(WeightedChoice {
             items: items,
             // we're likely to be generating numbers in this range
             // relatively often, so might as well cache it
             weight_range: Range::new(0, running_total)
-        }
+        })
     }
 }
 
@@ -136,22 +165,26 @@ impl<T: Clone> Distribution<T> for WeightedChoice<T> {
 mod tests {
     use mock::MockAddRng;
     use distributions::Distribution;
-    use super::{WeightedChoice, Weighted};
+    use super::{WeightedChoice, Weighted, WeightedError};
 
     #[test]
     fn test_weighted_choice() {
         // this makes assumptions about the internal implementation of
         // WeightedChoice, specifically: it doesn't reorder the items,
-        // it doesn't do weird things to the RNG (so 0 maps to 0, 1 to
-        // 1, internally; modulo a modulo operation).
+        // it doesn't do weird things to the RNG (so the RNG stepping by
+        // `ceil(2^32 / total_weight)` makes it pick weight 0, then 1, and
+        // so on; `Range` takes the high word of the random number times
+        // the total weight).
 
         macro_rules! t {
             ($items:expr, $expected:expr) => {{
                 let items = $items;
+                let total: u32 = items.iter().map(|item| item.weight).sum();
                 let wc = WeightedChoice::new(items);
                 let expected = $expected;
 
-                let mut rng = MockAddRng::new(0u32, 1);
+                // with a total weight of 1 the step wraps to 0, which is fine
+                let mut rng = MockAddRng::new(0u32, (::std::u32::MAX / total).wrapping_add(1));
 
                 for &val in expected.iter() {
                     assert_eq!(wc.sample(&mut rng), val)
@@ -234,4 +267,17 @@ mod tests {
                                   Weighted { weight: x, item: 2 },
                                   Weighted { weight: 1, item: 3 }]);
     }
+
+    #[test]
+    fn test_weighted_choice_try_new() {
+        assert_eq!(WeightedChoice::<isize>::try_new(vec![]).unwrap_err(),
+                   WeightedError::NoItems);
+        assert_eq!(WeightedChoice::try_new(vec![Weighted { weight: 0, item: 0 }]).unwrap_err(),
+                   WeightedError::ZeroTotalWeight);
+        let x = ::std::u32::MAX;
+        assert_eq!(WeightedChoice::try_new(vec![Weighted { weight: x, item: 0 },
+                                                Weighted { weight: 1, item: 1 }]).unwrap_err(),
+                   WeightedError::TotalWeightOverflow);
+        assert!(WeightedChoice::try_new(vec![Weighted { weight: x, item: 0 }]).is_ok());
+    }
 }